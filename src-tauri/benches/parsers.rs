@@ -0,0 +1,176 @@
+//! Parser throughput over corpora shaped like real Claude Code logs.
+//!
+//! Run with `cargo bench --bench parsers`; `BACKFILL_BENCH_MB=1024` sizes the
+//! backfill corpus (default 256 MiB).
+
+use agents_office_lib::parsing::{
+    determine_agent_status, determine_agent_type, merge_by_timestamp, parse_debug_line,
+    parse_files, parse_session_line, worker_count, LineParser,
+};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+
+/// Lines per corpus; the mix repeats with varying ids and paths.
+const CORPUS_LINES: usize = 5_000;
+/// Transcripts read in one simulated backfill
+const BACKFILL_FILES: usize = 200;
+/// Backfill corpus size unless `BACKFILL_BENCH_MB` says otherwise
+const BACKFILL_DEFAULT_MB: usize = 256;
+
+/// Transcript lines in roughly the proportions a coding session produces:
+/// tool calls and their results dominate, with assistant text, prompts and noise.
+fn session_corpus() -> Vec<String> {
+    (0..CORPUS_LINES)
+        .map(|i| {
+            let ts = format!("2025-01-01T{:02}:{:02}:{:02}.000Z", i / 3600 % 24, i / 60 % 60, i % 60);
+            match i % 10 {
+                0 => format!(
+                    r#"{{"type":"assistant","sessionId":"s1","timestamp":"{ts}","message":{{"role":"assistant","content":[{{"type":"tool_use","id":"toolu_{i}","name":"Read","input":{{"file_path":"/repo/src/module_{}.rs"}}}}]}}}}"#,
+                    i % 40
+                ),
+                1 | 3 | 5 => format!(
+                    r#"{{"type":"user","sessionId":"s1","timestamp":"{ts}","message":{{"role":"user","content":[{{"type":"tool_result","tool_use_id":"toolu_{i}","content":"{}"}}]}}}}"#,
+                    "fn handler() -> Result<(), Error> {\\n    Ok(())\\n}\\n".repeat(8)
+                ),
+                2 => format!(
+                    r#"{{"type":"assistant","sessionId":"s1","timestamp":"{ts}","message":{{"role":"assistant","content":[{{"type":"tool_use","id":"toolu_{i}","name":"Edit","input":{{"file_path":"/repo/src/lib.rs","old_string":"let x = 1;","new_string":"let x = compute({i});"}}}}]}}}}"#
+                ),
+                4 => format!(
+                    r#"{{"type":"assistant","sessionId":"s1","timestamp":"{ts}","message":{{"role":"assistant","content":[{{"type":"tool_use","id":"toolu_{i}","name":"Bash","input":{{"command":"cargo test --workspace -- module_{}"}}}}]}}}}"#,
+                    i % 40
+                ),
+                6 => format!(
+                    r#"{{"type":"assistant","sessionId":"s1","timestamp":"{ts}","message":{{"role":"assistant","content":[{{"type":"tool_use","id":"toolu_{i}","name":"Grep","input":{{"pattern":"fn \\w+_{}","path":"/repo"}}}}]}}}}"#,
+                    i % 40
+                ),
+                7 => format!(
+                    r#"{{"type":"assistant","sessionId":"s1","timestamp":"{ts}","message":{{"role":"assistant","content":[{{"type":"text","text":"The failing test expects the parser to keep partial lines between reads, so I will update the tracker and rerun the suite."}}]}}}}"#
+                ),
+                8 => format!(
+                    r#"{{"type":"user","sessionId":"s1","timestamp":"{ts}","message":{{"role":"user","content":"Please also cover the truncated file case ({i})."}}}}"#
+                ),
+                _ => r#"{"type":"summary","summary":"Parser fixes","leafUuid":"0000"}"#.to_string(),
+            }
+        })
+        .collect()
+}
+
+fn debug_corpus() -> Vec<String> {
+    (0..CORPUS_LINES)
+        .map(|i| {
+            let ts = format!(
+                "2025-01-01 {:02}:{:02}:{:02}",
+                i / 3600 % 24,
+                i / 60 % 60,
+                i % 60
+            );
+            match i % 6 {
+                0 => format!("{ts} Tool call: Read /repo/src/module_{}.rs", i % 40),
+                1 => format!("{ts} Tool result: Read (412 lines)"),
+                2 => format!("{ts} [DEBUG] Streaming response chunk {i} received"),
+                3 => format!("{ts} [ERROR] Request failed: connection reset by peer"),
+                4 => format!("{ts} Running Bash: cargo build --release"),
+                _ => format!("{ts} Updated TODO list with {} items", i % 7),
+            }
+        })
+        .collect()
+}
+
+fn bytes(lines: &[String]) -> u64 {
+    lines.iter().map(|l| l.len() as u64 + 1).sum()
+}
+
+fn bench_parsers(c: &mut Criterion) {
+    let session = session_corpus();
+    let debug = debug_corpus();
+
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(bytes(&session)));
+    group.bench_function("parse_session_line", |b| {
+        b.iter(|| {
+            for line in &session {
+                black_box(parse_session_line(black_box(line)));
+            }
+        })
+    });
+    group.throughput(Throughput::Bytes(bytes(&debug)));
+    group.bench_function("parse_debug_line", |b| {
+        b.iter(|| {
+            for line in &debug {
+                black_box(parse_debug_line(black_box(line)));
+            }
+        })
+    });
+    group.finish();
+
+    let entries: Vec<_> = session
+        .iter()
+        .filter_map(|l| parse_session_line(l))
+        .chain(debug.iter().filter_map(|l| parse_debug_line(l)))
+        .collect();
+    let mut group = c.benchmark_group("classify");
+    group.throughput(Throughput::Elements(entries.len() as u64));
+    group.bench_function("agent_type_and_status", |b| {
+        b.iter(|| {
+            for entry in &entries {
+                black_box(determine_agent_type(black_box(entry)));
+                black_box(determine_agent_status(black_box(entry)));
+            }
+        })
+    });
+    group.finish();
+}
+
+/// `BACKFILL_FILES` transcripts cycling through the session corpus, about `mb` MiB in total.
+fn backfill_corpus(mb: usize) -> Vec<Vec<String>> {
+    let session = session_corpus();
+    let per_file = mb * 1024 * 1024 / BACKFILL_FILES;
+    (0..BACKFILL_FILES)
+        .map(|_| {
+            let mut size = 0;
+            session
+                .iter()
+                .cycle()
+                .take_while(|line| {
+                    let keep = size < per_file;
+                    size += line.len() + 1;
+                    keep
+                })
+                .cloned()
+                .collect()
+        })
+        .collect()
+}
+
+/// Parse and merge many transcripts, serially and on the worker pool.
+fn bench_backfill(c: &mut Criterion) {
+    let mb = std::env::var("BACKFILL_BENCH_MB")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(BACKFILL_DEFAULT_MB);
+    let corpus = backfill_corpus(mb);
+    let files = || {
+        corpus
+            .iter()
+            .map(|lines| (lines.clone(), parse_session_line as LineParser))
+            .collect::<Vec<_>>()
+    };
+
+    let mut group = c.benchmark_group("backfill");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(corpus.iter().map(|f| bytes(f)).sum()));
+    let mut workers = vec![1, worker_count()];
+    workers.dedup();
+    for workers in workers {
+        group.bench_function(format!("workers_{workers}"), |b| {
+            b.iter_batched(
+                files,
+                |files| black_box(merge_by_timestamp(parse_files(files, workers))),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_parsers, bench_backfill);
+criterion_main!(benches);
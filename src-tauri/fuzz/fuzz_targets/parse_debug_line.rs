@@ -0,0 +1,21 @@
+#![no_main]
+
+use agents_office_lib::parsing::{
+    determine_agent_status, determine_agent_type, parse_debug_line, stitch_records,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let lines: Vec<String> = text.lines().map(String::from).collect();
+    let line_count = lines.len();
+    let records = stitch_records(lines);
+    assert!(records.len() <= line_count);
+    for record in records {
+        if let Some(entry) = parse_debug_line(&record) {
+            // Content is cut from the record, after any timestamp and level.
+            assert!(record.contains(entry.content.as_str()));
+            determine_agent_type(&entry);
+            determine_agent_status(&entry);
+        }
+    }
+});
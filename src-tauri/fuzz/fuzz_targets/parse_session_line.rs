@@ -0,0 +1,13 @@
+#![no_main]
+
+use agents_office_lib::parsing::{
+    determine_agent_status, determine_agent_type, parse_session_line,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|line: &str| {
+    if let Some(entry) = parse_session_line(line) {
+        determine_agent_type(&entry);
+        determine_agent_status(&entry);
+    }
+});
@@ -0,0 +1,114 @@
+use crate::models::{FileAccess, LogEntry, LogEntryType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Read/edit/write counts for one file in one session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileActivity {
+    pub session_id: String,
+    pub path: String,
+    pub reads: u32,
+    pub edits: u32,
+    pub writes: u32,
+    /// Timestamp of the most recent tool call on this file
+    pub last_touched: DateTime<Utc>,
+}
+
+impl FileActivity {
+    fn total(&self) -> u32 {
+        self.reads + self.edits + self.writes
+    }
+}
+
+/// Aggregates typed Read/Edit/Write tool inputs into per-session file counts
+pub struct FileActivityTracker {
+    /// (session id, path) -> counts
+    files: HashMap<(String, String), FileActivity>,
+}
+
+impl FileActivityTracker {
+    pub fn new() -> Self {
+        Self {
+            files: HashMap::new(),
+        }
+    }
+
+    pub fn observe(&mut self, entries: &[LogEntry]) {
+        for entry in entries {
+            if entry.entry_type != LogEntryType::ToolCall {
+                continue;
+            }
+            let Some((path, access)) = entry.tool_input.as_ref().and_then(|i| i.file_access())
+            else {
+                continue;
+            };
+            let session_id = entry.session_id.clone().unwrap_or_default();
+
+            let activity = self
+                .files
+                .entry((session_id.clone(), path.to_string()))
+                .or_insert_with(|| FileActivity {
+                    session_id,
+                    path: path.to_string(),
+                    reads: 0,
+                    edits: 0,
+                    writes: 0,
+                    last_touched: entry.timestamp,
+                });
+            match access {
+                FileAccess::Read => activity.reads += 1,
+                FileAccess::Edit => activity.edits += 1,
+                FileAccess::Write => activity.writes += 1,
+            }
+            activity.last_touched = entry.timestamp;
+        }
+    }
+
+    /// Files for one session (or all sessions), most-touched first.
+    pub fn snapshot(&self, session_id: Option<&str>) -> Vec<FileActivity> {
+        let mut files: Vec<FileActivity> = self
+            .files
+            .values()
+            .filter(|a| session_id.is_none_or(|s| a.session_id == s))
+            .cloned()
+            .collect();
+        files.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.path.cmp(&b.path)));
+        files
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn call(session: &str, tool: &str, path: &str) -> LogEntry {
+        LogEntry::test(LogEntryType::ToolCall)
+            .tool(tool)
+            .input(json!({ "file_path": path }))
+            .session(session)
+    }
+
+    #[test]
+    fn test_counts_per_session_and_file() {
+        let mut tracker = FileActivityTracker::new();
+        tracker.observe(&[
+            call("s1", "Read", "/a.rs"),
+            call("s1", "Edit", "/a.rs"),
+            call("s1", "Edit", "/a.rs"),
+            call("s1", "Write", "/b.rs"),
+            call("s2", "Read", "/a.rs"),
+            call("s1", "Bash", "/a.rs"),
+        ]);
+
+        let s1 = tracker.snapshot(Some("s1"));
+        assert_eq!(s1.len(), 2);
+        assert_eq!(
+            (s1[0].path.as_str(), s1[0].reads, s1[0].edits),
+            ("/a.rs", 1, 2)
+        );
+        assert_eq!(s1[1].writes, 1);
+        assert_eq!(tracker.snapshot(None).len(), 3);
+    }
+}
@@ -0,0 +1,45 @@
+//! Claude Code hook forwarder.
+//!
+//! Reads the hook JSON from stdin and POSTs it to the running app's ingest endpoint.
+//! Always exits 0 so a closed app never blocks or fails Claude's tool calls.
+
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+const DEFAULT_PORT: u16 = 4781;
+const TIMEOUT: Duration = Duration::from_millis(500);
+
+fn main() {
+    let mut body = String::new();
+    if std::io::stdin().read_to_string(&mut body).is_err() || body.trim().is_empty() {
+        return;
+    }
+
+    let port = std::env::var("AGENTS_OFFICE_INGEST_PORT")
+        .ok()
+        .and_then(|p| p.parse().ok())
+        .unwrap_or(DEFAULT_PORT);
+
+    if let Err(e) = forward(&body, port) {
+        eprintln!("agents-office-hook: {e}");
+    }
+}
+
+fn forward(body: &str, port: u16) -> std::io::Result<()> {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let mut stream = TcpStream::connect_timeout(&addr, TIMEOUT)?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+
+    write!(
+        stream,
+        "POST /hooks HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+
+    // Wait for the status line so the request is fully handled before Claude continues.
+    let mut response = [0u8; 64];
+    let _ = stream.read(&mut response)?;
+    Ok(())
+}
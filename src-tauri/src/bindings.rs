@@ -0,0 +1,90 @@
+//! Frontend bindings generated from the event models.
+//!
+//! `src/types/generated.ts` and `src/types/app-event.schema.json` are checked in;
+//! regenerate them with `UPDATE_BINDINGS=1 cargo test bindings`.
+
+use crate::events::{HudCounters, SubscriptionFilter, WatcherInfo};
+use crate::models::{
+    Agent, AgentState, AgentStatus, AgentType, AppEvent, Handoff, HandoffReason, LogEntry,
+    LogEntryType, SequencedEvent, TimestampSource, TodoDiff, TodoItem, TodoStatus,
+    TodoStatusChange, ToolDetail,
+};
+use crate::office::OfficeSnapshot;
+use std::path::PathBuf;
+use ts_rs::TS;
+
+const HEADER: &str = "// Generated from src-tauri/src/models by `UPDATE_BINDINGS=1 cargo test bindings`. Do not edit.\n";
+
+fn types_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("src")
+        .join("types")
+}
+
+fn render_typescript() -> String {
+    let decls = [
+        AgentType::decl(),
+        AgentStatus::decl(),
+        Agent::decl(),
+        AgentState::decl(),
+        LogEntryType::decl(),
+        TimestampSource::decl(),
+        ToolDetail::decl(),
+        LogEntry::decl(),
+        TodoStatus::decl(),
+        TodoItem::decl(),
+        TodoStatusChange::decl(),
+        TodoDiff::decl(),
+        HandoffReason::decl(),
+        Handoff::decl(),
+        AppEvent::decl(),
+        SequencedEvent::decl(),
+        SubscriptionFilter::decl(),
+        WatcherInfo::decl(),
+        HudCounters::decl(),
+        OfficeSnapshot::decl(),
+    ];
+    let mut out = HEADER.to_string();
+    for decl in decls {
+        out.push('\n');
+        out.push_str("export ");
+        out.push_str(&decl);
+        out.push('\n');
+    }
+    out
+}
+
+fn render_schema() -> String {
+    let schema = schemars::schema_for!(AppEvent);
+    serde_json::to_string_pretty(&schema).expect("schema serializes") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bindings_are_up_to_date() {
+        let files = [
+            (types_dir().join("generated.ts"), render_typescript()),
+            (types_dir().join("app-event.schema.json"), render_schema()),
+        ];
+
+        if std::env::var_os("UPDATE_BINDINGS").is_some() {
+            for (path, contents) in &files {
+                std::fs::write(path, contents).unwrap();
+            }
+            return;
+        }
+
+        for (path, contents) in &files {
+            let checked_in = std::fs::read_to_string(path).unwrap_or_default();
+            assert!(
+                checked_in == *contents,
+                "{} is stale; run `UPDATE_BINDINGS=1 cargo test bindings` in src-tauri",
+                path.display()
+            );
+        }
+    }
+}
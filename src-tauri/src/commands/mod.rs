@@ -1,4 +1,22 @@
-use crate::models::{Agent, AgentType};
+use crate::activity::{FileActivity, FileActivityTracker};
+use crate::events::{EventJournal, SubscriptionFilter, Subscriptions};
+use crate::export::{self, ExportQuery};
+use crate::filters::{EntryFilter, FilterSettings};
+use crate::history;
+use crate::models::{Agent, AgentType, SequencedEvent};
+use crate::notifications::NotificationCenter;
+use crate::office::{OfficeSnapshot, OfficeState};
+use crate::projects::{self, ProjectInfo};
+use crate::redact::Redactor;
+use crate::report::{render_html, SessionReport};
+use crate::search::{self, SearchPage, SearchQuery};
+use crate::settings::{AppSettings, SettingsStore};
+use crate::todos::{SessionTodos, TodoBoard};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::ipc::Channel;
+use tauri::State;
 
 /// Get the Claude home directory path
 #[tauri::command]
@@ -25,3 +43,196 @@ pub fn get_agents() -> Vec<Agent> {
         Agent::new("liaison".to_string(), AgentType::Liaison, (150.0, 520.0)),
     ]
 }
+
+/// Current agents, recent logs, sessions, watcher status and HUD counters,
+/// so a reloaded webview does not have to wait for the next file change
+#[tauri::command]
+pub fn get_office_snapshot(
+    journal: State<'_, Mutex<EventJournal>>,
+    office: State<'_, Mutex<OfficeState>>,
+) -> OfficeSnapshot {
+    // Agents are read under the journal lock so they are never older than `seq`.
+    let mut journal = journal.lock().expect("EventJournal mutex poisoned");
+    let now = Instant::now();
+    OfficeSnapshot {
+        seq: journal.seq(),
+        agents: office.lock().expect("OfficeState mutex poisoned").agents(),
+        logs: journal.recent_logs(),
+        sessions: journal.active_sessions(now),
+        watcher: journal.watcher(),
+        hud: journal.hud(now),
+    }
+}
+
+/// Buffered events after `seq`, for filling a gap in the event stream.
+///
+/// Fails when some of them were already dropped; call `get_office_snapshot` instead.
+#[tauri::command]
+pub fn get_events_since(
+    seq: u64,
+    journal: State<'_, Mutex<EventJournal>>,
+) -> Result<Vec<SequencedEvent>, String> {
+    journal
+        .lock()
+        .expect("EventJournal mutex poisoned")
+        .events_since(seq)
+        .ok_or_else(|| format!("Events after {seq} are no longer buffered; resync from a snapshot"))
+}
+
+/// Stream events matching `filter` to `on_event`; returns the subscription id.
+#[tauri::command]
+pub fn subscribe(
+    filter: SubscriptionFilter,
+    on_event: Channel<SequencedEvent>,
+    subscriptions: State<'_, Subscriptions>,
+) -> u32 {
+    subscriptions.add(filter, on_event)
+}
+
+/// Stop a `subscribe` stream; returns whether it was still open.
+#[tauri::command]
+pub fn unsubscribe(id: u32, subscriptions: State<'_, Subscriptions>) -> bool {
+    subscriptions.remove(id)
+}
+
+/// Get the persisted backend settings
+#[tauri::command]
+pub fn get_settings(store: State<'_, SettingsStore>) -> AppSettings {
+    store.get()
+}
+
+/// Persist backend settings.
+///
+/// Project, redaction, filter, notification and office settings apply right away; server,
+/// ingest, metrics and telemetry settings take effect after a restart.
+#[tauri::command]
+pub fn save_settings(
+    settings: AppSettings,
+    store: State<'_, SettingsStore>,
+    notifications: State<'_, NotificationCenter>,
+    redactor: State<'_, Mutex<Redactor>>,
+    filter: State<'_, Mutex<EntryFilter>>,
+    office: State<'_, Mutex<OfficeState>>,
+) -> Result<(), String> {
+    let new_redactor = Redactor::new(&settings.redaction)?;
+    let new_filter = EntryFilter::new(&settings.filters)?;
+    let (notification_settings, office_settings) =
+        (settings.notifications.clone(), settings.office.clone());
+    store.save(settings)?;
+
+    *redactor.lock().expect("Redactor mutex poisoned") = new_redactor;
+    *filter.lock().expect("EntryFilter mutex poisoned") = new_filter;
+    notifications.set_settings(notification_settings);
+    office
+        .lock()
+        .expect("OfficeState mutex poisoned")
+        .set_settings(office_settings);
+    Ok(())
+}
+
+/// Render a session transcript into a self-contained HTML report.
+///
+/// Writes to `output_path`, or `<Downloads>/agents-office-<session>.html` when omitted,
+/// and returns the path written.
+#[tauri::command]
+pub fn export_session_report(
+    session_id: String,
+    output_path: Option<String>,
+    redactor: State<'_, Mutex<Redactor>>,
+) -> Result<String, String> {
+    let transcript = history::find_session_file(&session_id)?;
+    let lines = history::read_lines(&transcript)?;
+    let redactor = redactor.lock().expect("Redactor mutex poisoned").clone();
+    let html = render_html(&SessionReport::from_lines(&session_id, &lines, &redactor));
+
+    let path = match output_path {
+        Some(path) => PathBuf::from(path),
+        None => dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .ok_or("Could not find a download directory")?
+            .join(format!("agents-office-{session_id}.html")),
+    };
+    std::fs::write(&path, html).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Export persisted entries for a session or time range as NDJSON, CSV or JSON.
+/// Returns the number of records written.
+#[tauri::command]
+pub fn export_entries(
+    query: ExportQuery,
+    output_path: String,
+    redactor: State<'_, Mutex<Redactor>>,
+) -> Result<usize, String> {
+    let redactor = redactor.lock().expect("Redactor mutex poisoned").clone();
+    export::export_to_file(&query, &PathBuf::from(output_path), &redactor)
+}
+
+/// Full-text search over persisted transcripts, newest first.
+/// Runs off the main thread since it scans every session file.
+#[tauri::command]
+pub async fn search_logs(
+    query: SearchQuery,
+    redactor: State<'_, Mutex<Redactor>>,
+) -> Result<SearchPage, String> {
+    let redactor = redactor.lock().expect("Redactor mutex poisoned").clone();
+    tauri::async_runtime::spawn_blocking(move || search::search(&query, &redactor))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Per-file read/edit/write counts seen since startup, most-touched first.
+/// Pass a session id to limit the result to one session.
+#[tauri::command]
+pub fn get_file_activity(
+    session_id: Option<String>,
+    tracker: State<'_, Mutex<FileActivityTracker>>,
+) -> Vec<FileActivity> {
+    tracker
+        .lock()
+        .expect("FileActivityTracker mutex poisoned")
+        .snapshot(session_id.as_deref())
+}
+
+/// Current TodoWrite plan per session (or for one session).
+#[tauri::command]
+pub fn get_todos(
+    session_id: Option<String>,
+    board: State<'_, Mutex<TodoBoard>>,
+) -> Vec<SessionTodos> {
+    board
+        .lock()
+        .expect("TodoBoard mutex poisoned")
+        .snapshot(session_id.as_deref())
+}
+
+/// Projects under `~/.claude/projects` with their sessions, most recently active first.
+#[tauri::command]
+pub async fn list_projects(store: State<'_, SettingsStore>) -> Result<Vec<ProjectInfo>, String> {
+    let settings = store.get().projects;
+    let root = history::projects_dir().ok_or("Could not find home directory")?;
+    tauri::async_runtime::spawn_blocking(move || projects::list_projects(&root, &settings))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Current include/exclude rules for watched entries
+#[tauri::command]
+pub fn get_filters(store: State<'_, SettingsStore>) -> FilterSettings {
+    store.get().filters
+}
+
+/// Replace the include/exclude rules; applies to the next watcher event and is persisted.
+#[tauri::command]
+pub fn save_filters(
+    filters: FilterSettings,
+    store: State<'_, SettingsStore>,
+    filter: State<'_, Mutex<EntryFilter>>,
+) -> Result<(), String> {
+    let compiled = EntryFilter::new(&filters)?;
+    let mut settings = store.get();
+    settings.filters = filters;
+    store.save(settings)?;
+    *filter.lock().expect("EntryFilter mutex poisoned") = compiled;
+    Ok(())
+}
@@ -0,0 +1,248 @@
+use crate::models::{AgentState, AppEvent, LogEntry, LogEntryType, TimestampSource};
+use std::time::{Duration, Instant};
+
+/// How often coalesced batches are flushed (about 20 frames per second)
+pub const FLUSH_INTERVAL: Duration = Duration::from_millis(50);
+/// Most log entries in one BatchUpdate
+const MAX_BATCH_LOGS: usize = 200;
+/// Approximate serialized size cap of one BatchUpdate
+const MAX_BATCH_BYTES: usize = 256 * 1024;
+/// Pending entries above which Message entries are shed, oldest first
+const MAX_PENDING_LOGS: usize = 2000;
+
+/// Batches and follow-up events ready to be emitted in order
+pub struct Flush {
+    pub events: Vec<AppEvent>,
+    /// Message entries shed under load since the last flush
+    pub dropped: usize,
+    /// When the oldest coalesced batch started processing
+    pub started_at: Instant,
+}
+
+/// Coalesces watcher and hook batches between flushes and splits them into
+/// bounded BatchUpdates.
+pub struct BatchEmitter {
+    logs: Vec<LogEntry>,
+    /// Latest state per agent, in first-seen order
+    agents: Vec<AgentState>,
+    /// Events emitted after the batch (hand-offs, todo updates)
+    followups: Vec<AppEvent>,
+    started_at: Option<Instant>,
+    dropped: usize,
+}
+
+impl BatchEmitter {
+    pub fn new() -> Self {
+        Self {
+            logs: Vec::new(),
+            agents: Vec::new(),
+            followups: Vec::new(),
+            started_at: None,
+            dropped: 0,
+        }
+    }
+
+    pub fn push(
+        &mut self,
+        logs: Vec<LogEntry>,
+        agents: Vec<AgentState>,
+        followups: Vec<AppEvent>,
+        started_at: Instant,
+    ) {
+        self.started_at = Some(self.started_at.map_or(started_at, |t| t.min(started_at)));
+        self.logs.extend(logs);
+        for agent in agents {
+            match self
+                .agents
+                .iter_mut()
+                .find(|a| a.agent.id == agent.agent.id)
+            {
+                Some(existing) => *existing = agent,
+                None => self.agents.push(agent),
+            }
+        }
+        self.followups.extend(followups);
+
+        if self.logs.len() > MAX_PENDING_LOGS {
+            self.shed_messages();
+        }
+    }
+
+    /// Drop the oldest assistant messages until the backlog fits; tool activity is kept.
+    fn shed_messages(&mut self) {
+        let excess = self.logs.len() - MAX_PENDING_LOGS;
+        let mut to_drop = excess;
+        self.logs.retain(|entry| {
+            if to_drop > 0 && entry.entry_type == LogEntryType::Message {
+                to_drop -= 1;
+                false
+            } else {
+                true
+            }
+        });
+        self.dropped += excess - to_drop;
+    }
+
+    /// Everything pending, split into size-capped BatchUpdates followed by the follow-ups.
+    pub fn take(&mut self) -> Option<Flush> {
+        let started_at = self.started_at.take()?;
+        let dropped = std::mem::take(&mut self.dropped);
+        let mut logs = std::mem::take(&mut self.logs);
+        if dropped > 0 {
+            logs.push(LogEntry {
+                timestamp: chrono::Utc::now(),
+                timestamp_source: TimestampSource::Ingested,
+                entry_type: LogEntryType::Message,
+                content: format!("{dropped} messages skipped under load"),
+                ..Default::default()
+            });
+        }
+
+        let mut agents = std::mem::take(&mut self.agents);
+        let mut events = Vec::new();
+        for chunk in split(logs) {
+            events.push(AppEvent::BatchUpdate {
+                logs: chunk,
+                agents: std::mem::take(&mut agents),
+            });
+        }
+        if !agents.is_empty() {
+            events.push(AppEvent::BatchUpdate {
+                logs: Vec::new(),
+                agents,
+            });
+        }
+        events.append(&mut self.followups);
+
+        Some(Flush {
+            events,
+            dropped,
+            started_at,
+        })
+    }
+}
+
+fn split(logs: Vec<LogEntry>) -> Vec<Vec<LogEntry>> {
+    let mut chunks = Vec::new();
+    let mut chunk = Vec::new();
+    let mut bytes = 0;
+    for entry in logs {
+        let size = estimated_size(&entry);
+        if !chunk.is_empty() && (chunk.len() == MAX_BATCH_LOGS || bytes + size > MAX_BATCH_BYTES) {
+            chunks.push(std::mem::take(&mut chunk));
+            bytes = 0;
+        }
+        bytes += size;
+        chunk.push(entry);
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+/// Rough JSON size: the free-text fields plus a fixed allowance for the rest
+pub(super) fn estimated_size(entry: &LogEntry) -> usize {
+    256 + entry.content.len() + entry.tool_detail.as_ref().map_or(0, |d| d.diff.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Agent, AgentStatus, AgentType};
+
+    fn batch_sizes(flush: &Flush) -> Vec<(usize, usize)> {
+        flush
+            .events
+            .iter()
+            .filter_map(|e| match e {
+                AppEvent::BatchUpdate { logs, agents } => Some((logs.len(), agents.len())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_coalesces_and_splits_batches() {
+        let mut emitter = BatchEmitter::new();
+        assert!(emitter.take().is_none());
+
+        let t0 = Instant::now();
+        let mut developer = AgentState::new(Agent::new(
+            "developer".to_string(),
+            AgentType::Developer,
+            (0.0, 0.0),
+        ));
+        emitter.push(
+            vec![LogEntry::test(LogEntryType::ToolCall).content("a"); 150],
+            vec![developer.clone()],
+            vec![],
+            t0 + Duration::from_millis(5),
+        );
+        developer.agent.status = AgentStatus::Working;
+        emitter.push(
+            vec![LogEntry::test(LogEntryType::ToolCall).content("b"); 150],
+            vec![developer],
+            vec![AppEvent::SessionEnd {
+                session_id: "s1".to_string(),
+            }],
+            t0,
+        );
+
+        let flush = emitter.take().unwrap();
+        assert_eq!(flush.started_at, t0);
+        assert_eq!(batch_sizes(&flush), [(MAX_BATCH_LOGS, 1), (100, 0)]);
+        let AppEvent::BatchUpdate { agents, .. } = &flush.events[0] else {
+            panic!("batch first");
+        };
+        assert_eq!(agents[0].agent.status, AgentStatus::Working);
+        assert!(matches!(flush.events[2], AppEvent::SessionEnd { .. }));
+        assert!(emitter.take().is_none());
+
+        let big = "x".repeat(MAX_BATCH_BYTES / 2);
+        emitter.push(
+            vec![LogEntry::test(LogEntryType::Message).content(&big); 3],
+            vec![],
+            vec![],
+            t0,
+        );
+        assert_eq!(
+            batch_sizes(&emitter.take().unwrap()),
+            [(1, 0), (1, 0), (1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_sheds_messages_under_load() {
+        let mut emitter = BatchEmitter::new();
+        let mut logs =
+            vec![LogEntry::test(LogEntryType::Message).content("chatter"); MAX_PENDING_LOGS];
+        logs.extend(vec![
+            LogEntry::test(LogEntryType::ToolCall).content("call");
+            10
+        ]);
+        emitter.push(logs, vec![], vec![], Instant::now());
+
+        let flush = emitter.take().unwrap();
+        assert_eq!(flush.dropped, 10);
+        let logs: Vec<LogEntry> = flush
+            .events
+            .into_iter()
+            .flat_map(|e| match e {
+                AppEvent::BatchUpdate { logs, .. } => logs,
+                _ => vec![],
+            })
+            .collect();
+        assert_eq!(logs.len(), MAX_PENDING_LOGS + 1);
+        assert_eq!(
+            logs.iter()
+                .filter(|e| e.entry_type == LogEntryType::ToolCall)
+                .count(),
+            10
+        );
+        assert_eq!(
+            logs.last().unwrap().content,
+            "10 messages skipped under load"
+        );
+    }
+}
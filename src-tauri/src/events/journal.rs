@@ -0,0 +1,337 @@
+use super::batcher::estimated_size;
+use crate::models::{AppEvent, LogEntry, LogEntryType, SequencedEvent};
+use crate::watcher::is_limit_reached_message;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Emitted events kept for `get_events_since`
+const MAX_BUFFERED_EVENTS: usize = 512;
+/// Approximate serialized size cap of the buffered events (about 32 full batches)
+const MAX_BUFFERED_BYTES: usize = 8 * 1024 * 1024;
+/// Log entries kept for late subscribers (matches the Inbox limit)
+const MAX_RECENT_LOGS: usize = 100;
+/// Window for the HUD counters (matches the frontend HUD)
+const HUD_WINDOW: Duration = Duration::from_secs(60);
+/// A session with no entries for this long is no longer listed as active
+const SESSION_IDLE: Duration = Duration::from_secs(600);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+pub struct WatcherInfo {
+    pub active: bool,
+    pub path: String,
+}
+
+/// Activity over the last 60 seconds
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+pub struct HudCounters {
+    pub tool_calls: u32,
+    pub tool_results: u32,
+    pub errors: u32,
+    pub handoffs: u32,
+    pub rate_limited: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HudKind {
+    ToolCall,
+    ToolResult,
+    Error,
+    Handoff,
+}
+
+/// What the webview had been told so far, rebuilt from every emitted event.
+pub struct EventJournal {
+    seq: u64,
+    /// Buffered events with their estimated size
+    events: VecDeque<(usize, SequencedEvent)>,
+    buffered_bytes: usize,
+    logs: VecDeque<LogEntry>,
+    sessions: HashMap<String, Instant>,
+    watcher: Option<WatcherInfo>,
+    hud: VecDeque<(Instant, HudKind)>,
+    rate_limited: bool,
+}
+
+impl EventJournal {
+    pub fn new() -> Self {
+        Self {
+            seq: 0,
+            events: VecDeque::new(),
+            buffered_bytes: 0,
+            logs: VecDeque::new(),
+            sessions: HashMap::new(),
+            watcher: None,
+            hud: VecDeque::new(),
+            rate_limited: false,
+        }
+    }
+
+    /// Record an event about to be emitted and stamp it with the next sequence number.
+    pub fn record(&mut self, event: AppEvent, now: Instant) -> SequencedEvent {
+        self.seq += 1;
+        match &event {
+            AppEvent::LogEntry(entry) => self.record_entry(entry, now),
+            AppEvent::BatchUpdate { logs, .. } => {
+                for entry in logs {
+                    self.record_entry(entry, now);
+                }
+            }
+            AppEvent::Handoff(_) => self.hud.push_back((now, HudKind::Handoff)),
+            AppEvent::SessionStart { session_id } => {
+                self.sessions.insert(session_id.clone(), now);
+            }
+            AppEvent::SessionEnd { session_id } => {
+                self.sessions.remove(session_id);
+            }
+            AppEvent::WatcherStatus { active, path } => {
+                self.watcher = Some(WatcherInfo {
+                    active: *active,
+                    path: path.clone(),
+                });
+            }
+            AppEvent::AgentUpdate(_) | AppEvent::TodoListUpdate { .. } => {}
+        }
+        self.prune(now);
+
+        let event = SequencedEvent {
+            seq: self.seq,
+            event,
+        };
+        let size = event_size(&event.event);
+        while !self.events.is_empty()
+            && (self.events.len() == MAX_BUFFERED_EVENTS
+                || self.buffered_bytes + size > MAX_BUFFERED_BYTES)
+        {
+            if let Some((dropped, _)) = self.events.pop_front() {
+                self.buffered_bytes -= dropped;
+            }
+        }
+        self.buffered_bytes += size;
+        self.events.push_back((size, event.clone()));
+        event
+    }
+
+    /// Events after `seq`, or `None` when some of them are no longer buffered.
+    pub fn events_since(&self, seq: u64) -> Option<Vec<SequencedEvent>> {
+        if seq > self.seq {
+            return None;
+        }
+        let oldest = self.events.front().map_or(self.seq + 1, |(_, e)| e.seq);
+        if seq + 1 < oldest {
+            return None;
+        }
+        Some(
+            self.events
+                .iter()
+                .filter(|(_, e)| e.seq > seq)
+                .map(|(_, e)| e.clone())
+                .collect(),
+        )
+    }
+
+    fn record_entry(&mut self, entry: &LogEntry, now: Instant) {
+        if self.logs.len() == MAX_RECENT_LOGS {
+            self.logs.pop_front();
+        }
+        self.logs.push_back(entry.clone());
+
+        if let Some(session_id) = &entry.session_id {
+            if entry.entry_type == LogEntryType::SessionEnd {
+                self.sessions.remove(session_id);
+            } else {
+                self.sessions.insert(session_id.clone(), now);
+            }
+        }
+
+        let kind = match entry.entry_type {
+            LogEntryType::ToolCall => Some(HudKind::ToolCall),
+            LogEntryType::ToolResult => Some(HudKind::ToolResult),
+            LogEntryType::Error => Some(HudKind::Error),
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            self.hud.push_back((now, kind));
+        }
+        if is_limit_reached_message(&entry.content) {
+            self.rate_limited = true;
+        } else if matches!(
+            entry.entry_type,
+            LogEntryType::ToolCall | LogEntryType::ToolResult
+        ) {
+            self.rate_limited = false;
+        }
+    }
+
+    fn prune(&mut self, now: Instant) {
+        while self
+            .hud
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > HUD_WINDOW)
+        {
+            self.hud.pop_front();
+        }
+        self.sessions
+            .retain(|_, last| now.duration_since(*last) <= SESSION_IDLE);
+    }
+
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Oldest first
+    pub fn recent_logs(&self) -> Vec<LogEntry> {
+        self.logs.iter().cloned().collect()
+    }
+
+    /// Most recently active first
+    pub fn active_sessions(&mut self, now: Instant) -> Vec<String> {
+        self.prune(now);
+        let mut sessions: Vec<(&String, &Instant)> = self.sessions.iter().collect();
+        sessions.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        sessions.into_iter().map(|(id, _)| id.clone()).collect()
+    }
+
+    pub fn watcher(&self) -> Option<WatcherInfo> {
+        self.watcher.clone()
+    }
+
+    pub fn hud(&mut self, now: Instant) -> HudCounters {
+        self.prune(now);
+        let count = |kind| self.hud.iter().filter(|(_, k)| *k == kind).count() as u32;
+        HudCounters {
+            tool_calls: count(HudKind::ToolCall),
+            tool_results: count(HudKind::ToolResult),
+            errors: count(HudKind::Error),
+            handoffs: count(HudKind::Handoff),
+            rate_limited: self.rate_limited,
+        }
+    }
+}
+
+/// Rough JSON size of an event, in the same terms as the batch size cap
+fn event_size(event: &AppEvent) -> usize {
+    match event {
+        AppEvent::LogEntry(entry) => estimated_size(entry),
+        AppEvent::BatchUpdate { logs, agents } => {
+            256 + logs.iter().map(estimated_size).sum::<usize>() + 256 * agents.len()
+        }
+        AppEvent::TodoListUpdate { todos, diff, .. } => {
+            let items = todos.iter().chain(&diff.added).chain(&diff.removed);
+            256 + items
+                .map(|t| 64 + t.content.len() + t.active_form.len())
+                .sum::<usize>()
+                + diff
+                    .status_changed
+                    .iter()
+                    .map(|c| 64 + c.content.len())
+                    .sum::<usize>()
+        }
+        _ => 256,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracks_logs_sessions_and_hud_window() {
+        let mut journal = EventJournal::new();
+        let t0 = Instant::now();
+
+        let logs = (0..MAX_RECENT_LOGS + 5)
+            .map(|_| LogEntry::test(LogEntryType::ToolCall).session("s1"))
+            .collect();
+        let stamped = journal.record(
+            AppEvent::BatchUpdate {
+                logs,
+                agents: vec![],
+            },
+            t0,
+        );
+        assert_eq!(stamped.seq, 1);
+        journal.record(
+            AppEvent::LogEntry(Box::new(LogEntry::test(LogEntryType::Error).session("s2"))),
+            t0 + Duration::from_secs(1),
+        );
+        assert_eq!(journal.seq(), 2);
+        assert_eq!(journal.recent_logs().len(), MAX_RECENT_LOGS);
+        assert_eq!(
+            journal.recent_logs().last().unwrap().entry_type,
+            LogEntryType::Error
+        );
+        assert_eq!(
+            journal.active_sessions(t0 + Duration::from_secs(2)),
+            ["s2", "s1"]
+        );
+
+        let hud = journal.hud(t0 + Duration::from_secs(2));
+        assert_eq!(
+            (hud.tool_calls, hud.errors),
+            (MAX_RECENT_LOGS as u32 + 5, 1)
+        );
+        let hud = journal.hud(t0 + Duration::from_secs(61));
+        assert_eq!((hud.tool_calls, hud.errors), (0, 1));
+
+        journal.record(
+            AppEvent::LogEntry(Box::new(
+                LogEntry::test(LogEntryType::SessionEnd).session("s2"),
+            )),
+            t0 + Duration::from_secs(62),
+        );
+        assert_eq!(
+            journal.active_sessions(t0 + Duration::from_secs(62)),
+            ["s1"]
+        );
+        assert!(journal
+            .active_sessions(t0 + Duration::from_secs(700))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_events_since_detects_unrecoverable_gaps() {
+        let mut journal = EventJournal::new();
+        let t0 = Instant::now();
+        assert_eq!(journal.events_since(0).unwrap().len(), 0);
+
+        for _ in 0..MAX_BUFFERED_EVENTS + 10 {
+            journal.record(
+                AppEvent::SessionStart {
+                    session_id: "s1".to_string(),
+                },
+                t0,
+            );
+        }
+        let latest = journal.seq();
+        let tail: Vec<u64> = journal
+            .events_since(latest - 2)
+            .unwrap()
+            .iter()
+            .map(|e| e.seq)
+            .collect();
+        assert_eq!(tail, [latest - 1, latest]);
+        assert!(journal.events_since(latest).unwrap().is_empty());
+        assert_eq!(journal.events_since(10).unwrap().len(), MAX_BUFFERED_EVENTS);
+        assert!(journal.events_since(9).is_none());
+        assert!(journal.events_since(latest + 1).is_none());
+
+        // Large batches are evicted by size long before the event count cap.
+        let big =
+            LogEntry::test(LogEntryType::Message).content(&"x".repeat(MAX_BUFFERED_BYTES / 4));
+        for _ in 0..6 {
+            journal.record(
+                AppEvent::BatchUpdate {
+                    logs: vec![big.clone()],
+                    agents: vec![],
+                },
+                t0,
+            );
+        }
+        assert!(journal.buffered_bytes <= MAX_BUFFERED_BYTES);
+        assert_eq!(journal.events_since(journal.seq() - 3).unwrap().len(), 3);
+        assert!(journal.events_since(journal.seq() - 4).is_none());
+    }
+}
@@ -0,0 +1,72 @@
+mod batcher;
+mod journal;
+mod outbox;
+mod subscriptions;
+
+pub use batcher::{BatchEmitter, Flush, FLUSH_INTERVAL};
+pub use journal::{EventJournal, HudCounters, WatcherInfo};
+pub use outbox::Outbox;
+pub use subscriptions::{SubscriptionFilter, Subscriptions};
+
+use crate::models::{AppEvent, SequencedEvent};
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tokio::sync::broadcast;
+
+/// Name of the Tauri event every `AppEvent` is emitted under
+pub const APP_EVENT: &str = "app-event";
+
+/// Buffered events per out-of-process subscriber before it starts lagging
+const HUB_CAPACITY: usize = 256;
+
+/// Fan-out point for `AppEvent`s going to consumers outside the webview
+/// (e.g. the browser server's WebSocket clients).
+pub struct EventHub {
+    sender: broadcast::Sender<SequencedEvent>,
+}
+
+impl EventHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(HUB_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<SequencedEvent> {
+        self.sender.subscribe()
+    }
+
+    fn publish(&self, event: &SequencedEvent) {
+        // Skip the clone entirely when nobody is listening.
+        if self.sender.receiver_count() > 0 {
+            let _ = self.sender.send(event.clone());
+        }
+    }
+}
+
+/// Stamp an event with the next sequence number and emit it to the webview,
+/// every hub subscriber and every matching `subscribe` channel.
+pub fn emit<R: Runtime>(app: &AppHandle<R>, event: AppEvent) {
+    let Some(outbox) = app.try_state::<Outbox>() else {
+        deliver(app, SequencedEvent { seq: 0, event });
+        return;
+    };
+    if let Some(journal) = app.try_state::<Mutex<EventJournal>>() {
+        // Queue while still holding the journal so the outbox stays in sequence order.
+        let mut journal = journal.lock().expect("EventJournal mutex poisoned");
+        outbox.push(journal.record(event, Instant::now()));
+    } else {
+        outbox.push(SequencedEvent { seq: 0, event });
+    }
+    outbox.drain(|event| deliver(app, event));
+}
+
+fn deliver<R: Runtime>(app: &AppHandle<R>, event: SequencedEvent) {
+    if let Some(hub) = app.try_state::<EventHub>() {
+        hub.publish(&event);
+    }
+    if let Some(subscriptions) = app.try_state::<Subscriptions>() {
+        subscriptions.send(&event);
+    }
+    let _ = app.emit(APP_EVENT, event);
+}
@@ -0,0 +1,120 @@
+use crate::models::SequencedEvent;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+#[derive(Default)]
+struct Queue {
+    events: VecDeque<SequencedEvent>,
+    /// Whether some thread is currently draining the queue
+    delivering: bool,
+}
+
+/// Sequenced events waiting to be delivered, in `seq` order.
+///
+/// Events are pushed while the journal lock is held, so the queue order matches
+/// the sequence numbers. Delivery happens outside that lock: whichever emitter
+/// finds the queue idle drains it, and the others just leave their events behind.
+pub struct Outbox {
+    queue: Mutex<Queue>,
+}
+
+impl Outbox {
+    pub fn new() -> Self {
+        Self {
+            queue: Mutex::new(Queue::default()),
+        }
+    }
+
+    pub fn push(&self, event: SequencedEvent) {
+        self.lock().events.push_back(event);
+    }
+
+    /// Deliver queued events one at a time, unless another thread is already doing so.
+    pub fn drain(&self, mut deliver: impl FnMut(SequencedEvent)) {
+        {
+            let mut queue = self.lock();
+            if queue.delivering {
+                return;
+            }
+            queue.delivering = true;
+        }
+        loop {
+            let next = {
+                let mut queue = self.lock();
+                let next = queue.events.pop_front();
+                if next.is_none() {
+                    queue.delivering = false;
+                }
+                next
+            };
+            match next {
+                Some(event) => deliver(event),
+                None => return,
+            }
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Queue> {
+        self.queue.lock().expect("Outbox mutex poisoned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::AppEvent;
+    use std::sync::Arc;
+
+    fn event(seq: u64) -> SequencedEvent {
+        SequencedEvent {
+            seq,
+            event: AppEvent::SessionStart {
+                session_id: "s1".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_nested_drain_leaves_events_to_the_active_deliverer() {
+        let outbox = Outbox::new();
+        outbox.push(event(1));
+        let mut delivered = Vec::new();
+        outbox.drain(|e| {
+            if e.seq == 1 {
+                outbox.push(event(2));
+                outbox.drain(|_| panic!("queue is already being drained"));
+            }
+            delivered.push(e.seq);
+        });
+        assert_eq!(delivered, [1, 2]);
+    }
+
+    #[test]
+    fn test_concurrent_emitters_deliver_in_order() {
+        let seq = Arc::new(Mutex::new(0u64));
+        let outbox = Arc::new(Outbox::new());
+        let delivered = Arc::new(Mutex::new(Vec::new()));
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let (seq, outbox, delivered) = (seq.clone(), outbox.clone(), delivered.clone());
+                std::thread::spawn(move || {
+                    for _ in 0..250 {
+                        {
+                            let mut seq = seq.lock().unwrap();
+                            *seq += 1;
+                            outbox.push(event(*seq));
+                        }
+                        outbox.drain(|e| delivered.lock().unwrap().push(e.seq));
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let delivered = delivered.lock().unwrap();
+        assert_eq!(*delivered, (1..=1000).collect::<Vec<u64>>());
+    }
+}
@@ -0,0 +1,202 @@
+use crate::models::{AppEvent, LogEntry, LogEntryType, SequencedEvent};
+use crate::watcher::{agent_id_for_type, determine_agent_type};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::ipc::Channel;
+
+/// What one subscriber wants to receive; empty lists match everything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(default)]
+pub struct SubscriptionFilter {
+    pub sessions: Vec<String>,
+    pub entry_types: Vec<LogEntryType>,
+    /// Office agent ids, e.g. `developer`
+    pub agent_ids: Vec<String>,
+}
+
+impl SubscriptionFilter {
+    fn session_matches(&self, session_id: Option<&str>) -> bool {
+        self.sessions.is_empty()
+            || session_id.is_some_and(|id| self.sessions.iter().any(|s| s == id))
+    }
+
+    fn agent_matches(&self, agent_id: &str) -> bool {
+        self.agent_ids.is_empty() || self.agent_ids.iter().any(|a| a == agent_id)
+    }
+
+    fn entry_matches(&self, entry: &LogEntry) -> bool {
+        self.session_matches(entry.session_id.as_deref())
+            && (self.entry_types.is_empty() || self.entry_types.contains(&entry.entry_type))
+            && self.agent_matches(agent_id_for_type(determine_agent_type(entry)))
+    }
+
+    /// The part of `event` this subscriber should see, if any.
+    ///
+    /// Batches are trimmed to matching logs and agents. `seq` stays global, so
+    /// filtered subscribers see gaps that are not missed events.
+    pub fn apply(&self, event: &SequencedEvent) -> Option<SequencedEvent> {
+        if self == &Self::default() {
+            return Some(event.clone());
+        }
+        let keep = match &event.event {
+            AppEvent::LogEntry(entry) => self.entry_matches(entry),
+            AppEvent::AgentUpdate(state) => self.agent_matches(&state.agent.id),
+            AppEvent::Handoff(handoff) => {
+                self.agent_matches(&handoff.from) || self.agent_matches(&handoff.to)
+            }
+            AppEvent::SessionStart { session_id }
+            | AppEvent::SessionEnd { session_id }
+            | AppEvent::TodoListUpdate { session_id, .. } => self.session_matches(Some(session_id)),
+            AppEvent::WatcherStatus { .. } => true,
+            AppEvent::BatchUpdate { logs, agents } => {
+                let logs: Vec<LogEntry> = logs
+                    .iter()
+                    .filter(|e| self.entry_matches(e))
+                    .cloned()
+                    .collect();
+                let agents: Vec<_> = agents
+                    .iter()
+                    .filter(|s| self.agent_matches(&s.agent.id))
+                    .cloned()
+                    .collect();
+                if logs.is_empty() && agents.is_empty() {
+                    return None;
+                }
+                return Some(SequencedEvent {
+                    seq: event.seq,
+                    event: AppEvent::BatchUpdate { logs, agents },
+                });
+            }
+        };
+        keep.then(|| event.clone())
+    }
+}
+
+/// Channels opened by the `subscribe` command, keyed by channel id
+pub struct Subscriptions {
+    channels: Mutex<HashMap<u32, (SubscriptionFilter, Channel<SequencedEvent>)>>,
+}
+
+impl Subscriptions {
+    pub fn new() -> Self {
+        Self {
+            channels: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn add(&self, filter: SubscriptionFilter, channel: Channel<SequencedEvent>) -> u32 {
+        let id = channel.id();
+        self.channels
+            .lock()
+            .expect("Subscriptions mutex poisoned")
+            .insert(id, (filter, channel));
+        id
+    }
+
+    pub fn remove(&self, id: u32) -> bool {
+        self.channels
+            .lock()
+            .expect("Subscriptions mutex poisoned")
+            .remove(&id)
+            .is_some()
+    }
+
+    /// Send `event` to every matching subscriber, dropping channels whose window is gone.
+    pub fn send(&self, event: &SequencedEvent) {
+        let mut channels = self.channels.lock().expect("Subscriptions mutex poisoned");
+        channels.retain(|id, (filter, channel)| {
+            let Some(event) = filter.apply(event) else {
+                return true;
+            };
+            match channel.send(event) {
+                Ok(()) => true,
+                Err(e) => {
+                    tracing::debug!("Dropping event subscription {}: {}", id, e);
+                    false
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Agent, AgentState, AgentType};
+
+    fn sequenced(event: AppEvent) -> SequencedEvent {
+        SequencedEvent { seq: 7, event }
+    }
+
+    #[test]
+    fn test_filter_trims_batches() {
+        let filter = SubscriptionFilter {
+            sessions: vec!["s1".to_string()],
+            agent_ids: vec!["developer".to_string()],
+            ..Default::default()
+        };
+        let batch = sequenced(AppEvent::BatchUpdate {
+            logs: vec![
+                LogEntry::test(LogEntryType::ToolCall)
+                    .tool("Edit")
+                    .session("s1"),
+                LogEntry::test(LogEntryType::ToolCall)
+                    .tool("Read")
+                    .session("s1"),
+                LogEntry::test(LogEntryType::ToolCall)
+                    .tool("Edit")
+                    .session("s2"),
+            ],
+            agents: vec![
+                AgentState::new(Agent::new(
+                    "developer".to_string(),
+                    AgentType::Developer,
+                    (0.0, 0.0),
+                )),
+                AgentState::new(Agent::new(
+                    "explorer".to_string(),
+                    AgentType::Explorer,
+                    (0.0, 0.0),
+                )),
+            ],
+        });
+
+        let Some(SequencedEvent {
+            seq: 7,
+            event: AppEvent::BatchUpdate { logs, agents },
+        }) = filter.apply(&batch)
+        else {
+            panic!("batch should match");
+        };
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].tool_name.as_deref(), Some("Edit"));
+        assert_eq!(agents.len(), 1);
+
+        let other_session = sequenced(AppEvent::SessionEnd {
+            session_id: "s2".to_string(),
+        });
+        assert!(filter.apply(&other_session).is_none());
+        assert!(SubscriptionFilter::default()
+            .apply(&other_session)
+            .is_some());
+    }
+
+    #[test]
+    fn test_closed_channels_are_dropped() {
+        let subscriptions = Subscriptions::new();
+        let open = subscriptions.add(SubscriptionFilter::default(), Channel::new(|_| Ok(())));
+        let closed = subscriptions.add(
+            SubscriptionFilter::default(),
+            Channel::new(|_| Err(tauri::Error::WebviewNotFound)),
+        );
+
+        subscriptions.send(&sequenced(AppEvent::WatcherStatus {
+            active: true,
+            path: "/".to_string(),
+        }));
+        assert!(!subscriptions.remove(closed));
+        assert!(subscriptions.remove(open));
+    }
+}
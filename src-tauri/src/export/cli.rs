@@ -0,0 +1,105 @@
+use crate::export::{collect_entries, export_to_file, write_entries, ExportQuery};
+use crate::redact::Redactor;
+use crate::settings::SettingsStore;
+use std::path::PathBuf;
+
+const USAGE: &str = "\
+Usage: agents-office export [options]
+
+Options:
+  --session <id>       Export a single session (default: all sessions)
+  --from <time>        Inclusive start, RFC 3339 or YYYY-MM-DD
+  --to <time>          Exclusive end, RFC 3339 or YYYY-MM-DD
+  --format <format>    ndjson (default), csv or json
+  --columns <a,b,...>  timestamp, session_id, entry_type, agent_type, agent_status,
+                       tool_name, tool_use_id, agent_id, project, git_branch,
+                       content (default: all)
+  --output <path>      Write to a file instead of stdout";
+
+/// `agents-office export ...`; returns the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    match parse_args(args).and_then(|(query, output)| export(&query, output)) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("agents-office export: {e}\n\n{USAGE}");
+            2
+        }
+    }
+}
+
+fn export(query: &ExportQuery, output: Option<PathBuf>) -> Result<(), String> {
+    let redactor = Redactor::new(&SettingsStore::load().get().redaction)?;
+    match output {
+        Some(path) => {
+            let count = export_to_file(query, &path, &redactor)?;
+            eprintln!("Exported {} entries to {}", count, path.display());
+        }
+        None => write_entries(
+            &collect_entries(query, &redactor)?,
+            query.format,
+            &query.columns(),
+            std::io::stdout().lock(),
+        )?,
+    }
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<(ExportQuery, Option<PathBuf>), String> {
+    let mut query = ExportQuery::default();
+    let mut output = None;
+    let mut args = args.iter();
+
+    while let Some(flag) = args.next() {
+        if flag == "-h" || flag == "--help" {
+            println!("{USAGE}");
+            std::process::exit(0);
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("Missing value for {flag}"))?
+            .clone();
+        match flag.as_str() {
+            "--session" => query.session_id = Some(value),
+            "--from" => query.from = Some(value),
+            "--to" => query.to = Some(value),
+            "--format" => query.format = value.parse()?,
+            "--columns" => {
+                query.columns = value.split(',').map(str::parse).collect::<Result<_, _>>()?
+            }
+            "--output" => output = Some(PathBuf::from(value)),
+            _ => return Err(format!("Unknown option {flag}")),
+        }
+    }
+    Ok((query, output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::{ExportColumn, ExportFormat};
+
+    #[test]
+    fn test_parse_args() {
+        let args: Vec<String> = [
+            "--session",
+            "s1",
+            "--format",
+            "CSV",
+            "--columns",
+            "timestamp,agent_type",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let (query, output) = parse_args(&args).unwrap();
+        assert_eq!(query.session_id.as_deref(), Some("s1"));
+        assert_eq!(query.format, ExportFormat::Csv);
+        assert_eq!(
+            query.columns,
+            vec![ExportColumn::Timestamp, ExportColumn::AgentType]
+        );
+        assert!(output.is_none());
+
+        assert!(parse_args(&["--columns".to_string(), "bogus".to_string()]).is_err());
+    }
+}
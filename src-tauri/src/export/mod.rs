@@ -0,0 +1,320 @@
+pub mod cli;
+
+use crate::history::{self, TimeRange};
+use crate::models::LogEntry;
+use crate::redact::Redactor;
+use crate::watcher::{determine_agent_status, determine_agent_type};
+use chrono::SecondsFormat;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Output encoding of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// One JSON object per line
+    #[default]
+    Ndjson,
+    Csv,
+    /// Pretty-printed JSON array
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_value(Value::String(s.to_ascii_lowercase()))
+            .map_err(|_| format!("Unknown format '{s}' (expected ndjson, csv or json)"))
+    }
+}
+
+/// A field of an exported record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportColumn {
+    Timestamp,
+    SessionId,
+    EntryType,
+    /// Office role the entry is routed to (same classification as the live view)
+    AgentType,
+    /// Status the routed agent takes on for this entry
+    AgentStatus,
+    ToolName,
+    ToolUseId,
+    AgentId,
+    /// Repository path decoded from the transcript's project folder
+    Project,
+    GitBranch,
+    Content,
+}
+
+impl ExportColumn {
+    pub const ALL: [ExportColumn; 11] = [
+        ExportColumn::Timestamp,
+        ExportColumn::SessionId,
+        ExportColumn::EntryType,
+        ExportColumn::AgentType,
+        ExportColumn::AgentStatus,
+        ExportColumn::ToolName,
+        ExportColumn::ToolUseId,
+        ExportColumn::AgentId,
+        ExportColumn::Project,
+        ExportColumn::GitBranch,
+        ExportColumn::Content,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportColumn::Timestamp => "timestamp",
+            ExportColumn::SessionId => "session_id",
+            ExportColumn::EntryType => "entry_type",
+            ExportColumn::AgentType => "agent_type",
+            ExportColumn::AgentStatus => "agent_status",
+            ExportColumn::ToolName => "tool_name",
+            ExportColumn::ToolUseId => "tool_use_id",
+            ExportColumn::AgentId => "agent_id",
+            ExportColumn::Project => "project",
+            ExportColumn::GitBranch => "git_branch",
+            ExportColumn::Content => "content",
+        }
+    }
+
+    fn value(self, entry: &LogEntry) -> Value {
+        let optional = |v: &Option<String>| v.clone().map(Value::String).unwrap_or(Value::Null);
+        match self {
+            ExportColumn::Timestamp => {
+                Value::String(entry.timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+            }
+            ExportColumn::SessionId => optional(&entry.session_id),
+            ExportColumn::EntryType => serde_json::to_value(entry.entry_type).unwrap_or_default(),
+            ExportColumn::AgentType => {
+                serde_json::to_value(determine_agent_type(entry)).unwrap_or_default()
+            }
+            ExportColumn::AgentStatus => {
+                serde_json::to_value(determine_agent_status(entry)).unwrap_or_default()
+            }
+            ExportColumn::ToolName => optional(&entry.tool_name),
+            ExportColumn::ToolUseId => optional(&entry.tool_use_id),
+            ExportColumn::AgentId => optional(&entry.agent_id),
+            ExportColumn::Project => optional(&entry.project),
+            ExportColumn::GitBranch => optional(&entry.git_branch),
+            ExportColumn::Content => Value::String(entry.content.clone()),
+        }
+    }
+}
+
+impl FromStr for ExportColumn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ExportColumn::ALL
+            .into_iter()
+            .find(|c| c.name() == s.trim())
+            .ok_or_else(|| format!("Unknown column '{s}'"))
+    }
+}
+
+/// Which entries to export and how
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportQuery {
+    /// Export one session; all sessions when omitted
+    pub session_id: Option<String>,
+    /// Inclusive lower bound (RFC 3339 or `YYYY-MM-DD`)
+    pub from: Option<String>,
+    /// Exclusive upper bound (RFC 3339 or `YYYY-MM-DD`)
+    pub to: Option<String>,
+    pub format: ExportFormat,
+    /// Columns in output order; all columns when empty
+    pub columns: Vec<ExportColumn>,
+}
+
+impl ExportQuery {
+    fn columns(&self) -> Vec<ExportColumn> {
+        if self.columns.is_empty() {
+            ExportColumn::ALL.to_vec()
+        } else {
+            self.columns.clone()
+        }
+    }
+}
+
+/// Load the persisted entries matching `query`, ordered by timestamp.
+pub fn collect_entries(query: &ExportQuery, redactor: &Redactor) -> Result<Vec<LogEntry>, String> {
+    let files = match &query.session_id {
+        Some(session_id) => vec![history::find_session_file(session_id)?],
+        None => history::projects_dir()
+            .map(|root| history::list_session_files(&root))
+            .unwrap_or_default(),
+    };
+    collect_files(query, files, redactor)
+}
+
+fn collect_files(
+    query: &ExportQuery,
+    files: Vec<PathBuf>,
+    redactor: &Redactor,
+) -> Result<Vec<LogEntry>, String> {
+    let range = TimeRange::parse(query.from.as_deref(), query.to.as_deref())?;
+
+    let mut entries = Vec::new();
+    for file in files {
+        let file_entries = match history::read_entries(&file, redactor) {
+            Ok(file_entries) => file_entries,
+            Err(e) => {
+                tracing::warn!("Skipping unreadable transcript in export: {}", e);
+                continue;
+            }
+        };
+        entries.extend(file_entries.into_iter().filter(|e| range.contains(e)));
+    }
+    // On equal times, entries with a logged time go before guessed ones.
+    entries.sort_by_key(|e| (e.timestamp, e.timestamp_source));
+    Ok(entries)
+}
+
+/// Write `entries` in `format`, keeping only `columns`.
+pub fn write_entries<W: Write>(
+    entries: &[LogEntry],
+    format: ExportFormat,
+    columns: &[ExportColumn],
+    mut out: W,
+) -> Result<(), String> {
+    let record = |entry: &LogEntry| -> serde_json::Map<String, Value> {
+        columns
+            .iter()
+            .map(|c| (c.name().to_string(), c.value(entry)))
+            .collect()
+    };
+
+    match format {
+        ExportFormat::Ndjson => {
+            for entry in entries {
+                serde_json::to_writer(&mut out, &record(entry)).map_err(|e| e.to_string())?;
+                out.write_all(b"\n").map_err(|e| e.to_string())?;
+            }
+        }
+        ExportFormat::Json => {
+            let records: Vec<_> = entries.iter().map(record).collect();
+            serde_json::to_writer_pretty(&mut out, &records).map_err(|e| e.to_string())?;
+            out.write_all(b"\n").map_err(|e| e.to_string())?;
+        }
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            writer
+                .write_record(columns.iter().map(|c| c.name()))
+                .map_err(|e| e.to_string())?;
+            for entry in entries {
+                writer
+                    .write_record(columns.iter().map(|c| match c.value(entry) {
+                        Value::String(s) => s,
+                        Value::Null => String::new(),
+                        other => other.to_string(),
+                    }))
+                    .map_err(|e| e.to_string())?;
+            }
+            writer.flush().map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+/// Run `query` and write the result to `path`; returns the number of records.
+pub fn export_to_file(
+    query: &ExportQuery,
+    path: &Path,
+    redactor: &Redactor,
+) -> Result<usize, String> {
+    let entries = collect_entries(query, redactor)?;
+    let file = std::fs::File::create(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    write_entries(
+        &entries,
+        query.format,
+        &query.columns(),
+        std::io::BufWriter::new(file),
+    )?;
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LogEntryType;
+
+    fn entries() -> Vec<LogEntry> {
+        vec![LogEntry::test(LogEntryType::ToolCall)
+            .at("2025-01-01T10:00:00Z")
+            .content("cargo test, \"quoted\"")
+            .tool("Bash")
+            .session("s1")]
+    }
+
+    fn render(format: ExportFormat, columns: &[ExportColumn]) -> String {
+        let mut out = Vec::new();
+        write_entries(&entries(), format, columns, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_csv_includes_classification_columns() {
+        let csv = render(
+            ExportFormat::Csv,
+            &[
+                ExportColumn::AgentType,
+                ExportColumn::AgentStatus,
+                ExportColumn::Content,
+            ],
+        );
+        assert_eq!(
+            csv,
+            "agent_type,agent_status,content\nvalidator,working,\"cargo test, \"\"quoted\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn test_ndjson_and_json_keep_selected_columns() {
+        let ndjson = render(
+            ExportFormat::Ndjson,
+            &[ExportColumn::SessionId, ExportColumn::ToolUseId],
+        );
+        assert_eq!(ndjson, "{\"session_id\":\"s1\",\"tool_use_id\":null}\n");
+
+        let json: Value =
+            serde_json::from_str(&render(ExportFormat::Json, &ExportColumn::ALL)).unwrap();
+        assert_eq!(json[0]["entry_type"], "tool_call");
+    }
+    #[test]
+    fn test_collect_skips_unreadable_files_and_sorts_by_time() {
+        let root =
+            std::env::temp_dir().join(format!("agents-office-export-{}", std::process::id()));
+        let dir = root.join("-Users-me-app");
+        std::fs::create_dir_all(&dir).unwrap();
+        let late = dir.join("s1.jsonl");
+        std::fs::write(
+            &late,
+            r#"{"timestamp":"2025-01-02T00:00:00Z","message":{"content":[{"type":"text","text":"late"}]}}"#,
+        )
+        .unwrap();
+        let early = dir.join("s2.jsonl");
+        std::fs::write(
+            &early,
+            r#"{"timestamp":"2025-01-01T00:00:00Z","message":{"content":[{"type":"text","text":"early"}]}}"#,
+        )
+        .unwrap();
+
+        let entries = collect_files(
+            &ExportQuery::default(),
+            vec![late, dir.join("missing.jsonl"), early],
+            &Redactor::disabled(),
+        )
+        .unwrap();
+        let contents: Vec<&str> = entries.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, ["early", "late"]);
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
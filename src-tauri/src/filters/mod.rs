@@ -0,0 +1,184 @@
+use crate::models::{LogEntry, LogEntryType};
+use crate::projects::project_display_name;
+use glob::Pattern;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One filter condition set; every condition that is present must match.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilterRule {
+    /// Glob over the source file path, e.g. `**/debug/*.txt` (never matches hook entries)
+    pub path: Option<String>,
+    /// Glob over the project path or its last component, e.g. `scratch-*`
+    pub project: Option<String>,
+    /// Glob over the tool name, e.g. `mcp__*__health*`
+    pub tool: Option<String>,
+    /// Any of these entry types
+    pub entry_types: Vec<LogEntryType>,
+}
+
+/// Which entries reach the office. Excludes win; when includes are set, an
+/// entry must match at least one of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FilterSettings {
+    pub include: Vec<FilterRule>,
+    pub exclude: Vec<FilterRule>,
+}
+
+struct CompiledRule {
+    path: Option<Pattern>,
+    project: Option<Pattern>,
+    tool: Option<Pattern>,
+    entry_types: Vec<LogEntryType>,
+}
+
+impl CompiledRule {
+    fn new(rule: &FilterRule) -> Result<Self, String> {
+        if rule == &FilterRule::default() {
+            return Err("Filter rule has no conditions".to_string());
+        }
+        let compile = |glob: &Option<String>| -> Result<Option<Pattern>, String> {
+            glob.as_deref()
+                .map(|g| Pattern::new(g).map_err(|e| format!("Invalid filter glob {g:?}: {e}")))
+                .transpose()
+        };
+        Ok(Self {
+            path: compile(&rule.path)?,
+            project: compile(&rule.project)?,
+            tool: compile(&rule.tool)?,
+            entry_types: rule.entry_types.clone(),
+        })
+    }
+
+    fn matches(&self, entry: &LogEntry, path: Option<&Path>) -> bool {
+        if let Some(glob) = &self.path {
+            if !path.is_some_and(|p| glob.matches_path(p)) {
+                return false;
+            }
+        }
+        if let Some(glob) = &self.project {
+            let Some(project) = &entry.project else {
+                return false;
+            };
+            if !glob.matches(project) && !glob.matches(&project_display_name(project)) {
+                return false;
+            }
+        }
+        if let Some(glob) = &self.tool {
+            if !entry.tool_name.as_deref().is_some_and(|t| glob.matches(t)) {
+                return false;
+            }
+        }
+        self.entry_types.is_empty() || self.entry_types.contains(&entry.entry_type)
+    }
+
+    fn is_path_only(&self) -> bool {
+        self.project.is_none() && self.tool.is_none() && self.entry_types.is_empty()
+    }
+}
+
+/// Compiled `FilterSettings`, applied before classification and batching
+pub struct EntryFilter {
+    include: Vec<CompiledRule>,
+    exclude: Vec<CompiledRule>,
+}
+
+impl EntryFilter {
+    pub fn new(settings: &FilterSettings) -> Result<Self, String> {
+        let compile = |rules: &[FilterRule]| -> Result<Vec<CompiledRule>, String> {
+            rules.iter().map(CompiledRule::new).collect()
+        };
+        Ok(Self {
+            include: compile(&settings.include)?,
+            exclude: compile(&settings.exclude)?,
+        })
+    }
+
+    /// Whether every entry of `path` would be excluded, so the file need not be parsed.
+    pub fn skips_file(&self, path: &Path) -> bool {
+        self.exclude.iter().any(|rule| {
+            rule.is_path_only() && rule.path.as_ref().is_some_and(|g| g.matches_path(path))
+        })
+    }
+
+    /// `path` is the source file, or `None` for hook entries.
+    pub fn allows(&self, entry: &LogEntry, path: Option<&Path>) -> bool {
+        if self.exclude.iter().any(|rule| rule.matches(entry, path)) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(|rule| rule.matches(entry, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excludes_win_over_includes() {
+        let filter = EntryFilter::new(&FilterSettings {
+            include: vec![FilterRule {
+                project: Some("app-*".to_string()),
+                ..Default::default()
+            }],
+            exclude: vec![
+                FilterRule {
+                    tool: Some("mcp__*__health*".to_string()),
+                    ..Default::default()
+                },
+                FilterRule {
+                    path: Some("**/debug/*.txt".to_string()),
+                    ..Default::default()
+                },
+            ],
+        })
+        .unwrap();
+
+        let transcript = Path::new("/home/me/.claude/projects/-repo/s1.jsonl");
+        let read = LogEntry::test(LogEntryType::ToolCall)
+            .tool("Read")
+            .project("/repo/app-web");
+        assert!(filter.allows(&read, Some(transcript)));
+        assert!(filter.allows(&read, None));
+
+        let health = LogEntry::test(LogEntryType::ToolCall)
+            .tool("mcp__db__health_check")
+            .project("/repo/app-web");
+        assert!(!filter.allows(&health, Some(transcript)));
+        assert!(!filter.allows(&read.clone().project("/repo/scratch"), Some(transcript)));
+
+        let debug_log = Path::new("/home/me/.claude/debug/s1.txt");
+        assert!(filter.skips_file(debug_log));
+        assert!(!filter.skips_file(transcript));
+    }
+
+    #[test]
+    fn test_entry_types_and_invalid_rules() {
+        let filter = EntryFilter::new(&FilterSettings {
+            exclude: vec![FilterRule {
+                entry_types: vec![LogEntryType::Message],
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(!filter.allows(&LogEntry::test(LogEntryType::Message).project("/a"), None));
+        assert!(filter.allows(&LogEntry::test(LogEntryType::Error).project("/a"), None));
+
+        let empty_rule = FilterSettings {
+            exclude: vec![FilterRule::default()],
+            ..Default::default()
+        };
+        assert!(EntryFilter::new(&empty_rule).is_err());
+        let bad_glob = FilterSettings {
+            include: vec![FilterRule {
+                tool: Some("[".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(EntryFilter::new(&bad_glob).is_err());
+    }
+}
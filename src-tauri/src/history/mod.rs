@@ -0,0 +1,176 @@
+use crate::models::LogEntry;
+use crate::projects::decode_project_dir;
+use crate::redact::Redactor;
+use crate::watcher::parse_session_line;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::path::{Path, PathBuf};
+
+/// `~/.claude/projects`, where Claude Code persists one JSONL transcript per session
+pub fn projects_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".claude").join("projects"))
+}
+
+/// Locate `<projects_dir>/<project>/<session_id>.jsonl`.
+pub fn find_session_file(session_id: &str) -> Result<PathBuf, String> {
+    let root = projects_dir().ok_or("Could not find home directory")?;
+    find_session_file_in(&root, session_id)
+}
+
+pub fn find_session_file_in(root: &Path, session_id: &str) -> Result<PathBuf, String> {
+    if session_id.is_empty() || session_id.contains(['/', '\\']) || session_id.contains("..") {
+        return Err(format!("Invalid session id: {session_id}"));
+    }
+
+    let file_name = format!("{session_id}.jsonl");
+    let projects = std::fs::read_dir(root).map_err(|e| format!("{}: {}", root.display(), e))?;
+    projects
+        .flatten()
+        .map(|project| project.path().join(&file_name))
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| format!("No transcript found for session {session_id}"))
+}
+
+/// Read a transcript as raw lines (blank lines skipped).
+pub fn read_lines(path: &Path) -> Result<Vec<String>, String> {
+    let raw = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(raw
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(String::from)
+        .collect())
+}
+
+/// Every `<projects_dir>/<project>/<session>.jsonl` transcript.
+pub fn list_session_files(root: &Path) -> Vec<PathBuf> {
+    let Ok(projects) = std::fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = projects
+        .flatten()
+        .filter_map(|project| std::fs::read_dir(project.path()).ok())
+        .flat_map(|sessions| sessions.flatten().map(|s| s.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Parse a transcript the way the live watcher does, including the file-stem session id
+/// fallback, the decoded project path and secret redaction.
+pub fn read_entries(path: &Path, redactor: &Redactor) -> Result<Vec<LogEntry>, String> {
+    let session = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let project = decode_project_dir(&project_name(path));
+    Ok(read_lines(path)?
+        .iter()
+        .filter_map(|line| parse_session_line(line))
+        .map(|mut entry| {
+            if entry.session_id.is_none() {
+                entry.session_id = Some(session.to_string());
+            }
+            entry.project = Some(project.clone());
+            redactor.redact_entry(&mut entry);
+            entry
+        })
+        .collect())
+}
+
+/// Project folder a transcript lives in (`~/.claude/projects/<project>/<session>.jsonl`)
+pub fn project_name(path: &Path) -> String {
+    path.parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Half-open `[from, to)` filter on entry timestamps
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimeRange {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl TimeRange {
+    /// Bounds are RFC 3339 timestamps or `YYYY-MM-DD` dates (UTC midnight).
+    pub fn parse(from: Option<&str>, to: Option<&str>) -> Result<Self, String> {
+        Ok(Self {
+            from: from.map(parse_bound).transpose()?,
+            to: to.map(parse_bound).transpose()?,
+        })
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        self.from.is_none() && self.to.is_none()
+    }
+
+    /// Entries without a parseable timestamp only pass an unbounded range.
+    pub fn contains(&self, entry: &LogEntry) -> bool {
+        if self.is_unbounded() {
+            return true;
+        }
+        let Some(t) = entry_time(entry) else {
+            return false;
+        };
+        self.from.is_none_or(|from| t >= from) && self.to.is_none_or(|to| t < to)
+    }
+}
+
+/// The entry's own time; `None` when it was only guessed from the file or ingest time.
+pub fn entry_time(entry: &LogEntry) -> Option<DateTime<Utc>> {
+    entry
+        .timestamp_source
+        .is_logged()
+        .then_some(entry.timestamp)
+}
+
+fn parse_bound(raw: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(t) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(t.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|t| t.and_utc())
+        .ok_or_else(|| format!("Invalid time '{raw}' (expected RFC 3339 or YYYY-MM-DD)"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LogEntryType;
+
+    #[test]
+    fn test_find_session_file_searches_projects() {
+        let root =
+            std::env::temp_dir().join(format!("agents-office-history-{}", std::process::id()));
+        let project = root.join("-Users-me-app");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(project.join("abc.jsonl"), "{}\n\n{}\n").unwrap();
+
+        let found = find_session_file_in(&root, "abc").unwrap();
+        assert_eq!(found, project.join("abc.jsonl"));
+        assert_eq!(read_lines(&found).unwrap().len(), 2);
+        assert_eq!(list_session_files(&root), vec![found.clone()]);
+        assert_eq!(
+            read_entries(&found, &Redactor::disabled()).unwrap()[0]
+                .session_id
+                .as_deref(),
+            Some("abc")
+        );
+        assert!(find_session_file_in(&root, "missing").is_err());
+        assert!(find_session_file_in(&root, "../abc").is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_time_range_filter() {
+        let entry = LogEntry::test(LogEntryType::Message).at("2025-01-01T10:00:00Z");
+        let range = |from, to| TimeRange::parse(from, to).unwrap();
+
+        assert!(range(Some("2025-01-01"), Some("2025-01-02")).contains(&entry));
+        assert!(!range(Some("2025-01-02"), None).contains(&entry));
+        assert!(!range(Some("2025-01-01"), None).contains(&LogEntry::default()));
+        assert!(range(None, None).contains(&LogEntry::default()));
+        assert!(TimeRange::parse(Some("yesterday"), None).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::models::{LogEntry, LogEntryType};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long a delivered tool event suppresses its twin from the other source.
+/// Generous enough to cover the debouncer plus slow transcript flushes.
+const DEDUP_TTL: Duration = Duration::from_secs(120);
+
+/// Where an entry entered the pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntrySource {
+    /// Tailed transcript or debug log file
+    File,
+    /// Pushed by a Claude Code hook
+    Hook,
+}
+
+/// Drops tool events already delivered by the other source
+/// (hooks arrive first, the tailed transcript repeats them a moment later).
+///
+/// Repeats from the same source are kept: running `ls` twice is two calls.
+pub struct Deduplicator {
+    seen: HashMap<String, (Instant, EntrySource)>,
+}
+
+impl Deduplicator {
+    pub fn new() -> Self {
+        Self {
+            seen: HashMap::new(),
+        }
+    }
+
+    pub fn retain_new(
+        &mut self,
+        entries: Vec<LogEntry>,
+        source: EntrySource,
+        now: Instant,
+    ) -> Vec<LogEntry> {
+        self.seen
+            .retain(|_, (seen_at, _)| now.duration_since(*seen_at) < DEDUP_TTL);
+
+        entries
+            .into_iter()
+            .filter(|entry| {
+                let keys = dedup_keys(entry);
+
+                if keys.iter().any(|k| self.seen_from_other(k, source)) {
+                    // Consume the twin so a later genuine repeat is not swallowed.
+                    for key in &keys {
+                        if self.seen_from_other(key, source) {
+                            self.seen.remove(key);
+                        }
+                    }
+                    return false;
+                }
+
+                for key in keys {
+                    self.seen.insert(key, (now, source));
+                }
+                true
+            })
+            .collect()
+    }
+
+    fn seen_from_other(&self, key: &str, source: EntrySource) -> bool {
+        self.seen.get(key).is_some_and(|(_, s)| *s != source)
+    }
+}
+
+/// Identities of a tool event across sources: the tool_use id when known, and
+/// for calls also the name + input summary (hooks from older Claude Code lack ids).
+fn dedup_keys(entry: &LogEntry) -> Vec<String> {
+    let mut keys = Vec::new();
+    match entry.entry_type {
+        LogEntryType::ToolCall => {
+            if let Some(id) = &entry.tool_use_id {
+                keys.push(format!("call:{id}"));
+            }
+            if let Some(name) = &entry.tool_name {
+                keys.push(format!("call:{name}:{}", entry.content));
+            }
+        }
+        LogEntryType::ToolResult | LogEntryType::Error => {
+            if let Some(id) = &entry.tool_use_id {
+                keys.push(format!("result:{id}"));
+            }
+        }
+        _ => {}
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(id: Option<&str>, content: &str) -> LogEntry {
+        let call = LogEntry::test(LogEntryType::ToolCall)
+            .tool("Bash")
+            .content(content);
+        match id {
+            Some(id) => call.tool_use_id(id),
+            None => call,
+        }
+    }
+
+    #[test]
+    fn test_drops_transcript_copy_of_hook_event() {
+        let mut dedup = Deduplicator::new();
+        let now = Instant::now();
+
+        let kept = dedup.retain_new(vec![call(Some("toolu_1"), "ls")], EntrySource::Hook, now);
+        assert_eq!(kept.len(), 1);
+        let kept = dedup.retain_new(vec![call(Some("toolu_1"), "ls")], EntrySource::File, now);
+        assert!(kept.is_empty());
+    }
+
+    #[test]
+    fn test_fingerprint_matches_hook_without_id() {
+        let mut dedup = Deduplicator::new();
+        let now = Instant::now();
+
+        dedup.retain_new(vec![call(None, "ls")], EntrySource::Hook, now);
+        let kept = dedup.retain_new(vec![call(Some("toolu_1"), "ls")], EntrySource::File, now);
+        assert!(kept.is_empty());
+    }
+
+    #[test]
+    fn test_keeps_repeats_from_same_source_and_after_ttl() {
+        let mut dedup = Deduplicator::new();
+        let now = Instant::now();
+
+        let kept = dedup.retain_new(
+            vec![call(None, "ls"), call(None, "ls")],
+            EntrySource::Hook,
+            now,
+        );
+        assert_eq!(kept.len(), 2);
+
+        let later = now + DEDUP_TTL + Duration::from_secs(1);
+        let kept = dedup.retain_new(vec![call(None, "ls")], EntrySource::File, later);
+        assert_eq!(kept.len(), 1);
+    }
+}
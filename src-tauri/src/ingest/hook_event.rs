@@ -0,0 +1,145 @@
+use crate::models::{LogEntry, LogEntryType, TimestampSource, ToolInput};
+use crate::projects::decode_project_dir;
+use crate::watcher::{summarize_tool_input, tool_detail};
+use serde::Deserialize;
+use std::path::Path;
+
+/// JSON a Claude Code hook receives on stdin (fields vary per `hook_event_name`)
+#[derive(Debug, Clone, Deserialize)]
+pub struct HookEvent {
+    pub hook_event_name: String,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub tool_name: Option<String>,
+    #[serde(default)]
+    pub tool_use_id: Option<String>,
+    #[serde(default)]
+    pub tool_input: Option<serde_json::Value>,
+    #[serde(default)]
+    pub tool_response: Option<serde_json::Value>,
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// `~/.claude/projects/<project>/<session>.jsonl` of the hooked session
+    #[serde(default)]
+    pub transcript_path: Option<String>,
+}
+
+impl HookEvent {
+    /// Convert to the same `LogEntry` shape the transcript parser produces.
+    /// Returns `None` for hook events the office does not visualize.
+    pub fn into_log_entry(self) -> Option<LogEntry> {
+        let (entry_type, content) = match self.hook_event_name.as_str() {
+            "PreToolUse" => (
+                LogEntryType::ToolCall,
+                self.tool_input
+                    .as_ref()
+                    .map(summarize_tool_input)
+                    .unwrap_or_default(),
+            ),
+            "PostToolUse" => {
+                let failed = self
+                    .tool_response
+                    .as_ref()
+                    .and_then(|r| r.get("is_error").or_else(|| r.get("error")))
+                    .is_some_and(|v| v.as_bool().unwrap_or(!v.is_null()));
+                (
+                    if failed {
+                        LogEntryType::Error
+                    } else {
+                        LogEntryType::ToolResult
+                    },
+                    self.tool_response
+                        .as_ref()
+                        .map(response_text)
+                        .unwrap_or_default(),
+                )
+            }
+            "Notification" => (LogEntryType::Message, self.message.unwrap_or_default()),
+            "SubagentStop" => (LogEntryType::ToolResult, "Subagent finished".to_string()),
+            // `Stop` fires at the end of every turn; only `SessionEnd` ends the session.
+            "Stop" => (LogEntryType::Message, "Turn finished".to_string()),
+            "SessionEnd" => (LogEntryType::SessionEnd, "Session ended".to_string()),
+            _ => return None,
+        };
+
+        let tool_name = match self.hook_event_name.as_str() {
+            "SubagentStop" => Some("Task".to_string()),
+            _ => self.tool_name,
+        };
+
+        let tool_input = match (&entry_type, &tool_name, &self.tool_input) {
+            (LogEntryType::ToolCall, Some(name), Some(input)) => {
+                Some(ToolInput::parse(name, input))
+            }
+            _ => None,
+        };
+
+        Some(LogEntry {
+            timestamp: chrono::Utc::now(),
+            timestamp_source: TimestampSource::Ingested,
+            entry_type,
+            content,
+            agent_id: None,
+            tool_name,
+            tool_use_id: self.tool_use_id,
+            session_id: self.session_id,
+            project: self
+                .transcript_path
+                .as_deref()
+                .and_then(|p| Path::new(p).parent()?.file_name())
+                .map(|dir| decode_project_dir(&dir.to_string_lossy()))
+                .or_else(|| self.cwd.clone()),
+            cwd: self.cwd,
+            git_branch: None,
+            tool_detail: tool_input.as_ref().and_then(tool_detail),
+            tool_input,
+        })
+    }
+}
+
+/// Short text for a PostToolUse response (string, `stdout`, or compact JSON).
+fn response_text(response: &serde_json::Value) -> String {
+    let text = response
+        .as_str()
+        .or_else(|| response.get("stdout").and_then(|v| v.as_str()))
+        .map(String::from)
+        .unwrap_or_else(|| response.to_string());
+    text.chars().take(500).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &str) -> Option<LogEntry> {
+        serde_json::from_str::<HookEvent>(raw)
+            .unwrap()
+            .into_log_entry()
+    }
+
+    #[test]
+    fn test_pre_tool_use_becomes_tool_call() {
+        let entry = parse(
+            r#"{"hook_event_name":"PreToolUse","session_id":"s1","cwd":"/repo","tool_name":"Bash","tool_use_id":"toolu_01","tool_input":{"command":"npm test"}}"#,
+        )
+        .unwrap();
+        assert_eq!(entry.entry_type, LogEntryType::ToolCall);
+        assert_eq!(entry.tool_name.as_deref(), Some("Bash"));
+        assert_eq!(entry.content, "npm test");
+        assert_eq!(entry.tool_use_id.as_deref(), Some("toolu_01"));
+        assert_eq!(entry.session_id.as_deref(), Some("s1"));
+        assert_eq!(entry.project.as_deref(), Some("/repo"));
+    }
+
+    #[test]
+    fn test_stop_and_unknown_events() {
+        let stop = parse(r#"{"hook_event_name":"Stop","stop_hook_active":false}"#).unwrap();
+        assert_eq!(stop.entry_type, LogEntryType::Message);
+        let end = parse(r#"{"hook_event_name":"SessionEnd","reason":"exit"}"#).unwrap();
+        assert_eq!(end.entry_type, LogEntryType::SessionEnd);
+        assert!(parse(r#"{"hook_event_name":"UserPromptSubmit"}"#).is_none());
+    }
+}
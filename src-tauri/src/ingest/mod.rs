@@ -0,0 +1,134 @@
+mod dedup;
+mod hook_event;
+
+pub use dedup::{Deduplicator, EntrySource};
+pub use hook_event::HookEvent;
+
+use crate::filters::EntryFilter;
+use crate::history;
+use crate::projects::project_id_for;
+use crate::server::host_allowed;
+use crate::settings::SettingsStore;
+use crate::watcher;
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+
+/// Loopback endpoint receiving Claude Code hook payloads (see `agents-office-hook`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IngestSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for IngestSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            port: 4781,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct IngestState {
+    app: AppHandle,
+    port: u16,
+}
+
+/// Accept `POST /hooks` on 127.0.0.1 only; hook payloads are never exposed beyond loopback.
+pub async fn start_ingest_server(
+    app: AppHandle,
+    settings: IngestSettings,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.port));
+    let router = Router::new()
+        .route("/hooks", post(receive_hook))
+        .with_state(IngestState {
+            app,
+            port: settings.port,
+        });
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("Hook ingest listening on http://{}/hooks", addr);
+    axum::serve(listener, router).await?;
+    Ok(())
+}
+
+async fn receive_hook(
+    State(state): State<IngestState>,
+    headers: HeaderMap,
+    Json(event): Json<HookEvent>,
+) -> StatusCode {
+    if !request_allowed(&headers, state.port) {
+        tracing::warn!("Rejected hook request with foreign Host or Origin");
+        return StatusCode::FORBIDDEN;
+    }
+    tracing::debug!("Hook received: {}", event.hook_event_name);
+    let app = state.app;
+    let project_id = event
+        .transcript_path
+        .as_deref()
+        .and_then(|path| project_id_for(&history::projects_dir()?, Path::new(path)));
+    let Some(entry) = event.into_log_entry() else {
+        return StatusCode::NO_CONTENT;
+    };
+    let watched = project_id.as_deref().is_none_or(|id| {
+        app.try_state::<SettingsStore>()
+            .is_none_or(|s| s.get().projects.is_watched(id))
+    });
+    let allowed = app.try_state::<Mutex<EntryFilter>>().is_none_or(|f| {
+        f.lock()
+            .expect("EntryFilter mutex poisoned")
+            .allows(&entry, None)
+    });
+    if watched && allowed {
+        watcher::emit_entries(&app, vec![entry], EntrySource::Hook, Instant::now());
+    }
+    StatusCode::NO_CONTENT
+}
+
+/// Only the loopback forwarder may post: the Host must name this endpoint (no DNS
+/// rebinding) and browsers, which always send an Origin, are turned away.
+fn request_allowed(headers: &HeaderMap, port: u16) -> bool {
+    let host_ok = headers
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .is_some_and(|host| host_allowed(host, "127.0.0.1", port));
+    host_ok && !headers.contains_key(header::ORIGIN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(host: &str, origin: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, HeaderValue::from_str(host).unwrap());
+        if let Some(origin) = origin {
+            headers.insert(header::ORIGIN, HeaderValue::from_str(origin).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_rejects_rebound_hosts_and_browser_origins() {
+        assert!(request_allowed(&headers("127.0.0.1:4781", None), 4781));
+        assert!(request_allowed(&headers("localhost:4781", None), 4781));
+        assert!(!request_allowed(&headers("evil.example:4781", None), 4781));
+        assert!(!request_allowed(&headers("127.0.0.1:4780", None), 4781));
+        assert!(!request_allowed(
+            &headers("127.0.0.1:4781", Some("http://127.0.0.1:4781")),
+            4781
+        ));
+    }
+}
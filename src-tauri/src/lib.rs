@@ -1,9 +1,62 @@
+mod activity;
+#[cfg(test)]
+mod bindings;
 mod commands;
+mod events;
+mod export;
+mod filters;
+mod history;
+mod ingest;
+mod metrics;
 mod models;
+mod notifications;
+mod office;
+mod projects;
+mod redact;
+mod report;
+mod search;
+mod server;
+mod settings;
+mod telemetry;
+mod todos;
 mod watcher;
 
+/// Log line parsers and the backfill pool, exposed for `benches/` and the `fuzz/` targets
+#[doc(hidden)]
+pub mod parsing {
+    pub use crate::models::LogEntry;
+    pub use crate::watcher::{
+        determine_agent_status, determine_agent_type, merge_by_timestamp, parse_debug_line,
+        parse_files, parse_session_line, stitch_records, worker_count, LineParser,
+    };
+}
+
+use activity::FileActivityTracker;
+use events::{BatchEmitter, EventHub, EventJournal, Outbox, Subscriptions};
+use filters::EntryFilter;
+use ingest::Deduplicator;
+use metrics::Metrics;
+use models::AppEvent;
+use notifications::{NotificationCenter, TauriNotifier};
+use office::OfficeState;
+use redact::{RedactionSettings, Redactor};
+use settings::SettingsStore;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use telemetry::TraceExporter;
+use todos::TodoBoard;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+/// Handle a CLI subcommand (`agents-office export ...`) without starting the GUI.
+/// Returns the exit code, or `None` when no subcommand was given.
+pub fn run_cli(args: &[String]) -> Option<i32> {
+    match args.first().map(String::as_str) {
+        Some("export") => Some(export::cli::run(&args[1..])),
+        _ => None,
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tracing_subscriber::registry()
@@ -12,9 +65,112 @@ pub fn run() {
         .init();
 
     tauri::Builder::default()
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             let handle = app.handle().clone();
 
+            let settings = SettingsStore::load();
+            app.manage(NotificationCenter::new(
+                Box::new(TauriNotifier::new(handle.clone())),
+                settings.get().notifications,
+            ));
+            let server_settings = settings.get().server;
+            let ingest_settings = settings.get().ingest;
+            let metrics_settings = settings.get().metrics;
+            let telemetry_settings = settings.get().telemetry;
+            let redactor = Redactor::new(&settings.get().redaction).unwrap_or_else(|e| {
+                // Keep the built-in detectors even if a user pattern is broken.
+                tracing::warn!("{}; using built-in redaction only", e);
+                Redactor::new(&RedactionSettings::default()).expect("built-in redaction")
+            });
+            let filter = EntryFilter::new(&settings.get().filters).unwrap_or_else(|e| {
+                tracing::warn!("{}; watching without filters", e);
+                EntryFilter::new(&Default::default()).expect("empty filter")
+            });
+            let office = OfficeState::new(settings.get().office);
+            let metrics = Arc::new(Metrics::new());
+            app.manage(settings);
+            app.manage(EventHub::new());
+            app.manage(Mutex::new(EventJournal::new()));
+            app.manage(Outbox::new());
+            app.manage(Subscriptions::new());
+            app.manage(Mutex::new(BatchEmitter::new()));
+            app.manage(Mutex::new(Deduplicator::new()));
+            app.manage(Mutex::new(FileActivityTracker::new()));
+            app.manage(Mutex::new(TodoBoard::new()));
+            app.manage(Mutex::new(office));
+            app.manage(Mutex::new(redactor));
+            app.manage(Mutex::new(filter));
+            app.manage(metrics.clone());
+
+            // Export sessions as OpenTelemetry traces
+            if telemetry_settings.enabled {
+                match TraceExporter::new(&telemetry_settings) {
+                    Ok(exporter) => {
+                        app.manage(exporter);
+                        let flush_handle = handle.clone();
+                        std::thread::spawn(move || loop {
+                            std::thread::sleep(Duration::from_secs(5));
+                            flush_handle.state::<TraceExporter>().flush();
+                        });
+                    }
+                    Err(e) => tracing::error!("Failed to start trace exporter: {}", e),
+                }
+            }
+
+            // Loopback Prometheus endpoint for Grafana dashboards
+            if metrics_settings.enabled {
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = metrics::start_metrics_server(metrics, metrics_settings).await {
+                        tracing::error!("Failed to start metrics server: {}", e);
+                    }
+                });
+            }
+
+            // Receive Claude Code hook events pushed by `agents-office-hook`
+            if ingest_settings.enabled {
+                let ingest_handle = handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) =
+                        ingest::start_ingest_server(ingest_handle, ingest_settings).await
+                    {
+                        tracing::error!("Failed to start hook ingest: {}", e);
+                    }
+                });
+            }
+
+            // Optionally expose the office to browsers (second monitor, team TV)
+            if server_settings.enabled {
+                let server_handle = handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = server::start_server(server_handle, server_settings).await {
+                        tracing::error!("Failed to start browser server: {}", e);
+                    }
+                });
+            }
+
+            // Coalesce watcher and hook batches into frame-sized updates
+            let flush_handle = handle.clone();
+            std::thread::spawn(move || loop {
+                std::thread::sleep(events::FLUSH_INTERVAL);
+                watcher::flush_batches(&flush_handle);
+            });
+
+            // Evaluate time-based notification triggers and agent timeouts
+            let ticker_handle = handle.clone();
+            std::thread::spawn(move || loop {
+                std::thread::sleep(Duration::from_secs(1));
+                ticker_handle.state::<NotificationCenter>().tick();
+                let expired = ticker_handle
+                    .state::<Mutex<OfficeState>>()
+                    .lock()
+                    .expect("OfficeState mutex poisoned")
+                    .tick(Instant::now());
+                for state in expired {
+                    events::emit(&ticker_handle, AppEvent::AgentUpdate(state));
+                }
+            });
+
             // Start the log watcher in a background task
             tauri::async_runtime::spawn_blocking(move || {
                 if let Err(e) = watcher::start_watching(handle) {
@@ -27,6 +183,20 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             commands::get_claude_home,
             commands::get_agents,
+            commands::get_office_snapshot,
+            commands::get_events_since,
+            commands::subscribe,
+            commands::unsubscribe,
+            commands::get_settings,
+            commands::save_settings,
+            commands::export_session_report,
+            commands::export_entries,
+            commands::search_logs,
+            commands::get_file_activity,
+            commands::get_todos,
+            commands::list_projects,
+            commands::get_filters,
+            commands::save_filters,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,335 @@
+use crate::models::{LogEntry, LogEntryType};
+use crate::watcher::{agent_id_for_type, determine_agent_type, is_limit_reached_message};
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use chrono::{DateTime, Utc};
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts,
+    Registry, TextEncoder,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A session counts as active if its log was written within this window.
+const ACTIVE_SESSION_WINDOW: Duration = Duration::from_secs(5 * 60);
+
+/// Calls without a result after this long are dropped from latency tracking.
+const PENDING_TOOL_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Loopback Prometheus exporter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for MetricsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 9464,
+        }
+    }
+}
+
+struct PendingTool {
+    tool: String,
+    /// When the call was logged; latency is measured between log timestamps
+    called_at: DateTime<Utc>,
+    /// When the call was seen, for expiring calls that never get a result
+    seen_at: Instant,
+}
+
+/// Counters and histograms derived from the backend event stream
+pub struct Metrics {
+    registry: Registry,
+    tool_calls: IntCounterVec,
+    tool_duration: HistogramVec,
+    errors: IntCounterVec,
+    lines_read: IntCounter,
+    entries_parsed: IntCounter,
+    active_sessions: IntGauge,
+    batch_emit: Histogram,
+    dropped_entries: IntCounter,
+    /// Tool calls waiting for a result, keyed by tool_use id (or tool name as a fallback)
+    pending_tools: Mutex<HashMap<String, PendingTool>>,
+    session_activity: Mutex<HashMap<String, Instant>>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let tool_calls = IntCounterVec::new(
+            Opts::new(
+                "agents_office_tool_calls_total",
+                "Tool calls by tool and agent type",
+            ),
+            &["tool", "agent_type"],
+        )
+        .expect("valid tool_calls metric");
+        let tool_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "agents_office_tool_duration_seconds",
+                "Time from tool call to tool result",
+            )
+            .buckets(vec![0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 300.0]),
+            &["tool"],
+        )
+        .expect("valid tool_duration metric");
+        let errors = IntCounterVec::new(
+            Opts::new("agents_office_errors_total", "Errors by kind"),
+            &["kind"],
+        )
+        .expect("valid errors metric");
+        let lines_read = IntCounter::new(
+            "agents_office_lines_read_total",
+            "Log lines read by the file tracker",
+        )
+        .expect("valid lines_read metric");
+        let entries_parsed = IntCounter::new(
+            "agents_office_entries_parsed_total",
+            "Log lines parsed into entries",
+        )
+        .expect("valid entries_parsed metric");
+        let active_sessions = IntGauge::new(
+            "agents_office_active_sessions",
+            "Sessions with log activity in the last 5 minutes",
+        )
+        .expect("valid active_sessions metric");
+        let batch_emit = Histogram::with_opts(
+            HistogramOpts::new(
+                "agents_office_batch_emit_seconds",
+                "Time from reading a batch to emitting it, including coalescing",
+            )
+            .buckets(vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0]),
+        )
+        .expect("valid batch_emit metric");
+        let dropped_entries = IntCounter::new(
+            "agents_office_dropped_entries_total",
+            "Message entries skipped to keep up with the log backlog",
+        )
+        .expect("valid dropped_entries metric");
+
+        let registry = Registry::new();
+        for collector in [
+            Box::new(tool_calls.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(tool_duration.clone()),
+            Box::new(errors.clone()),
+            Box::new(lines_read.clone()),
+            Box::new(entries_parsed.clone()),
+            Box::new(active_sessions.clone()),
+            Box::new(batch_emit.clone()),
+            Box::new(dropped_entries.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric registered once");
+        }
+
+        Self {
+            registry,
+            tool_calls,
+            tool_duration,
+            errors,
+            lines_read,
+            entries_parsed,
+            active_sessions,
+            batch_emit,
+            dropped_entries,
+            pending_tools: Mutex::new(HashMap::new()),
+            session_activity: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record one file read by the watcher; `session` is the log file stem.
+    pub fn record_file_read(&self, session: &str, lines_read: usize, entries_parsed: usize) {
+        self.lines_read.inc_by(lines_read as u64);
+        self.entries_parsed.inc_by(entries_parsed as u64);
+        if lines_read > 0 {
+            self.session_activity
+                .lock()
+                .expect("Metrics session mutex poisoned")
+                .insert(session.to_string(), Instant::now());
+        }
+    }
+
+    pub fn observe_entries(&self, entries: &[LogEntry], now: Instant) {
+        let mut pending = self
+            .pending_tools
+            .lock()
+            .expect("Metrics pending mutex poisoned");
+        pending.retain(|_, call| now.duration_since(call.seen_at) < PENDING_TOOL_TTL);
+        // Hook entries never go through `record_file_read`, so count their sessions here.
+        let mut sessions = self
+            .session_activity
+            .lock()
+            .expect("Metrics session mutex poisoned");
+
+        for entry in entries {
+            if let Some(session_id) = &entry.session_id {
+                sessions.insert(session_id.clone(), now);
+            }
+            match entry.entry_type {
+                LogEntryType::ToolCall => {
+                    let tool = entry.tool_name.as_deref().unwrap_or("unknown");
+                    let agent_type = agent_id_for_type(determine_agent_type(entry));
+                    self.tool_calls.with_label_values(&[tool, agent_type]).inc();
+                    pending.insert(
+                        pending_key(entry),
+                        PendingTool {
+                            tool: tool.to_string(),
+                            called_at: entry.timestamp,
+                            seen_at: now,
+                        },
+                    );
+                }
+                LogEntryType::ToolResult | LogEntryType::Error => {
+                    // A backfilled call and result arrive together, so only their
+                    // timestamps say how long the tool ran.
+                    if let Some(call) = pending.remove(&pending_key(entry)) {
+                        if let Ok(elapsed) = (entry.timestamp - call.called_at).to_std() {
+                            self.tool_duration
+                                .with_label_values(&[&call.tool])
+                                .observe(elapsed.as_secs_f64());
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            if let Some(kind) = error_kind(entry) {
+                self.errors.with_label_values(&[kind]).inc();
+            }
+        }
+    }
+
+    pub fn observe_batch_emit(&self, elapsed: Duration) {
+        self.batch_emit.observe(elapsed.as_secs_f64());
+    }
+
+    pub fn record_dropped(&self, count: usize) {
+        self.dropped_entries.inc_by(count as u64);
+    }
+
+    /// Encode all metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        {
+            let mut sessions = self
+                .session_activity
+                .lock()
+                .expect("Metrics session mutex poisoned");
+            sessions.retain(|_, last| last.elapsed() < ACTIVE_SESSION_WINDOW);
+            self.active_sessions.set(sessions.len() as i64);
+        }
+
+        let mut buf = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buf) {
+            tracing::warn!("Failed to encode metrics: {}", e);
+        }
+        String::from_utf8(buf).unwrap_or_default()
+    }
+}
+
+fn pending_key(entry: &LogEntry) -> String {
+    match (&entry.tool_use_id, &entry.tool_name) {
+        (Some(id), _) => id.clone(),
+        (None, Some(name)) => name.clone(),
+        (None, None) => String::new(),
+    }
+}
+
+fn error_kind(entry: &LogEntry) -> Option<&'static str> {
+    if is_limit_reached_message(&entry.content) {
+        return Some("rate_limit");
+    }
+    if entry.entry_type != LogEntryType::Error {
+        return None;
+    }
+    Some(if entry.tool_use_id.is_some() {
+        "tool"
+    } else {
+        "log"
+    })
+}
+
+/// Serve `GET /metrics` on 127.0.0.1.
+pub async fn start_metrics_server(
+    metrics: Arc<Metrics>,
+    settings: MetricsSettings,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, settings.port));
+    let router = Router::new()
+        .route("/metrics", get(serve_metrics))
+        .with_state(metrics);
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("Metrics available at http://{}/metrics", addr);
+    axum::serve(listener, router).await?;
+    Ok(())
+}
+
+async fn serve_metrics(State(metrics): State<Arc<Metrics>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)],
+        metrics.render(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_call_and_latency_are_exported() {
+        let metrics = Metrics::new();
+        let call = LogEntry::test(LogEntryType::ToolCall)
+            .tool("Read")
+            .tool_use_id("toolu_1")
+            .at("2025-01-01T00:00:00Z");
+        let result = LogEntry::test(LogEntryType::ToolResult)
+            .tool_use_id("toolu_1")
+            .at("2025-01-01T00:00:03Z");
+
+        // Both arrive in one backfill batch; latency comes from the log timestamps.
+        metrics.observe_entries(&[call, result], Instant::now());
+        metrics.record_file_read("session-a", 4, 2);
+
+        let text = metrics.render();
+        assert!(
+            text.contains(r#"agents_office_tool_calls_total{agent_type="explorer",tool="Read"} 1"#)
+        );
+        assert!(text.contains(r#"agents_office_tool_duration_seconds_count{tool="Read"} 1"#));
+        assert!(text.contains(r#"agents_office_tool_duration_seconds_sum{tool="Read"} 3"#));
+        assert!(text.contains("agents_office_lines_read_total 4"));
+        assert!(text.contains("agents_office_active_sessions 1"));
+    }
+
+    #[test]
+    fn test_errors_are_counted_by_kind() {
+        let metrics = Metrics::new();
+        let rate_limited =
+            LogEntry::test(LogEntryType::Message).content("Claude usage limit reached");
+
+        metrics.observe_entries(
+            &[
+                LogEntry::test(LogEntryType::Error)
+                    .tool_use_id("toolu_2")
+                    .session("hook-only"),
+                rate_limited,
+            ],
+            Instant::now(),
+        );
+
+        let text = metrics.render();
+        assert!(text.contains(r#"agents_office_errors_total{kind="tool"} 1"#));
+        assert!(text.contains(r#"agents_office_errors_total{kind="rate_limit"} 1"#));
+        // Sessions seen only through entries (e.g. hooks) still count as active.
+        assert!(text.contains("agents_office_active_sessions 1"));
+    }
+}
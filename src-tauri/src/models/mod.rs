@@ -1,7 +1,13 @@
+mod tool_input;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+pub use tool_input::{FileAccess, TodoItem, TodoStatus, ToolInput};
+
 /// Type of agent in the office (workflow-based)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum AgentType {
     /// File exploration (Read, Glob)
@@ -11,6 +17,7 @@ pub enum AgentType {
     /// Planning and task management (TodoWrite, Task)
     Architect,
     /// Code writing (Write, Edit, NotebookEdit)
+    #[default]
     Developer,
     /// Command execution (Bash general)
     Operator,
@@ -22,14 +29,9 @@ pub enum AgentType {
     Liaison,
 }
 
-impl Default for AgentType {
-    fn default() -> Self {
-        Self::Developer
-    }
-}
-
 /// Current status of an agent
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum AgentStatus {
     #[default]
@@ -41,7 +43,8 @@ pub enum AgentStatus {
 }
 
 /// An agent in the office
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
 pub struct Agent {
     pub id: String,
     pub agent_type: AgentType,
@@ -62,22 +65,145 @@ impl Agent {
     }
 }
 
+/// Everything the office knows about one agent, including mood inputs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+pub struct AgentState {
+    pub agent: Agent,
+    /// Timestamp of the latest entry routed to this agent
+    #[cfg_attr(test, ts(type = "string | null"), schemars(with = "Option<String>"))]
+    pub last_activity: Option<DateTime<Utc>>,
+    #[cfg_attr(test, ts(type = "string | null"), schemars(with = "Option<String>"))]
+    pub last_tool_call: Option<DateTime<Utc>>,
+    #[cfg_attr(test, ts(type = "string | null"), schemars(with = "Option<String>"))]
+    pub last_error: Option<DateTime<Utc>>,
+    /// Errors since the last successful tool call or result
+    pub error_count: u32,
+    /// The usage limit was reached and no activity has resumed yet
+    pub rate_limited: bool,
+}
+
+impl AgentState {
+    /// An agent with no activity yet
+    pub fn new(agent: Agent) -> Self {
+        Self {
+            agent,
+            last_activity: None,
+            last_tool_call: None,
+            last_error: None,
+            error_count: 0,
+            rate_limited: false,
+        }
+    }
+}
+
 /// A log entry from Claude Code
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
 pub struct LogEntry {
-    pub timestamp: String,
+    #[cfg_attr(test, ts(type = "string"), schemars(with = "String"))]
+    pub timestamp: DateTime<Utc>,
+    /// How `timestamp` was derived
+    #[serde(default)]
+    pub timestamp_source: TimestampSource,
     pub entry_type: LogEntryType,
     pub content: String,
     pub agent_id: Option<String>,
     pub tool_name: Option<String>,
+    /// Claude's `toolu_...` id linking a tool call to its result (transcripts and hooks only)
+    #[serde(default)]
+    pub tool_use_id: Option<String>,
+    /// Claude session the entry belongs to (transcript `sessionId`, hook payload, or log file stem)
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Repository path decoded from the `~/.claude/projects/<project>` folder
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Working directory recorded with the entry (transcript `cwd` or hook payload)
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Transcript `gitBranch`
+    #[serde(default)]
+    pub git_branch: Option<String>,
+    /// Typed tool_use input (tool calls only); backend-side, not sent over IPC
+    #[serde(skip)]
+    pub tool_input: Option<ToolInput>,
+    /// Size-capped preview of what an Edit/MultiEdit/Write call changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_detail: Option<ToolDetail>,
+}
+
+/// Test fixtures: `LogEntry::test(ToolCall).tool("Read").session("s1")`
+#[cfg(test)]
+impl LogEntry {
+    pub fn test(entry_type: LogEntryType) -> Self {
+        Self {
+            entry_type,
+            ..Default::default()
+        }
+    }
+
+    /// Logged at `timestamp` (RFC 3339)
+    pub fn at(mut self, timestamp: &str) -> Self {
+        self.timestamp = timestamp.parse().expect("RFC 3339 timestamp");
+        self.timestamp_source = TimestampSource::Logged;
+        self
+    }
+
+    pub fn content(mut self, content: &str) -> Self {
+        self.content = content.to_string();
+        self
+    }
+
+    pub fn tool(mut self, tool: &str) -> Self {
+        self.tool_name = Some(tool.to_string());
+        self
+    }
+
+    /// Typed input for the tool set with `tool`
+    pub fn input(mut self, input: serde_json::Value) -> Self {
+        let tool = self.tool_name.as_deref().expect("set the tool first");
+        self.tool_input = Some(ToolInput::parse(tool, &input));
+        self
+    }
+
+    pub fn tool_use_id(mut self, id: &str) -> Self {
+        self.tool_use_id = Some(id.to_string());
+        self
+    }
+
+    pub fn session(mut self, session_id: &str) -> Self {
+        self.session_id = Some(session_id.to_string());
+        self
+    }
+
+    pub fn project(mut self, project: &str) -> Self {
+        self.project = Some(project.to_string());
+        self
+    }
+}
+
+/// Compact unified diff of a file-changing tool call
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+pub struct ToolDetail {
+    pub file_path: String,
+    /// Unified diff hunks (`@@ ... @@`, ` `/`-`/`+` lines), capped in size
+    pub diff: String,
+    pub lines_added: u32,
+    pub lines_removed: u32,
+    /// The diff text was cut to fit the size cap (line counts are still exact)
+    pub truncated: bool,
 }
 
 /// Type of log entry
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum LogEntryType {
     ToolCall,
     ToolResult,
+    #[default]
     Message,
     Error,
     TodoUpdate,
@@ -85,15 +211,104 @@ pub enum LogEntryType {
     SessionEnd,
 }
 
+/// Where a `LogEntry` timestamp came from, most reliable first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampSource {
+    /// Written in the log with an explicit offset (transcripts, ISO debug lines)
+    Logged,
+    /// Written in the log without an offset and read as the machine's local time
+    LocalTime,
+    /// Not in the log; the file's modification time
+    FileModified,
+    /// Not in the log; when the backend received the entry (hooks, summaries).
+    /// The default, so an entry built without a source is never taken as logged.
+    #[default]
+    Ingested,
+}
+
+impl TimestampSource {
+    /// The time was read from the entry itself rather than guessed.
+    pub fn is_logged(self) -> bool {
+        matches!(self, Self::Logged | Self::LocalTime)
+    }
+}
+
+/// Why work moved between agents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum HandoffReason {
+    /// A file touched by one agent was picked up by another
+    SharedFile,
+    /// A Task call delegated work that another agent started on
+    Delegation,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+pub struct Handoff {
+    pub from: String,
+    pub to: String,
+    pub reason: HandoffReason,
+    /// The shared file path, or the delegated task description
+    pub artifact: Option<String>,
+    /// Tool the receiving agent called
+    pub tool_name: Option<String>,
+}
+
 /// Event sent to the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
 #[serde(tag = "type", content = "payload")]
 pub enum AppEvent {
-    LogEntry(LogEntry),
-    AgentUpdate(Agent),
+    LogEntry(Box<LogEntry>),
+    AgentUpdate(AgentState),
     SessionStart { session_id: String },
     SessionEnd { session_id: String },
     WatcherStatus { active: bool, path: String },
     /// Batch update for performance - sends multiple logs and agents in one IPC call
-    BatchUpdate { logs: Vec<LogEntry>, agents: Vec<Agent> },
+    BatchUpdate {
+        logs: Vec<LogEntry>,
+        agents: Vec<AgentState>,
+    },
+    /// Work moved from one agent to another; `from` shows Passing for a moment
+    Handoff(Handoff),
+    /// A session's TodoWrite plan changed
+    TodoListUpdate {
+        session_id: String,
+        todos: Vec<TodoItem>,
+        diff: TodoDiff,
+    },
+}
+
+/// An `AppEvent` stamped with its position in the emitted stream.
+///
+/// Serialized flat (`{"seq": 1, "type": ..., "payload": ...}`); a jump in `seq`
+/// means events were missed and can be fetched with `get_events_since`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+pub struct SequencedEvent {
+    #[cfg_attr(test, ts(type = "number"))]
+    pub seq: u64,
+    #[serde(flatten)]
+    pub event: AppEvent,
+}
+
+/// Changes between two TodoWrite plans, matched by item content
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+pub struct TodoDiff {
+    pub added: Vec<TodoItem>,
+    pub removed: Vec<TodoItem>,
+    pub status_changed: Vec<TodoStatusChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+pub struct TodoStatusChange {
+    pub content: String,
+    pub from: TodoStatus,
+    pub to: TodoStatus,
 }
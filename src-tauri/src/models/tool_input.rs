@@ -0,0 +1,218 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReadInput {
+    pub file_path: String,
+    #[serde(default)]
+    pub offset: Option<u64>,
+    #[serde(default)]
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WriteInput {
+    pub file_path: String,
+    #[serde(default)]
+    pub content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditInput {
+    pub file_path: String,
+    #[serde(default)]
+    pub old_string: String,
+    #[serde(default)]
+    pub new_string: String,
+    #[serde(default)]
+    pub replace_all: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditOperation {
+    #[serde(default)]
+    pub old_string: String,
+    #[serde(default)]
+    pub new_string: String,
+    #[serde(default)]
+    pub replace_all: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultiEditInput {
+    pub file_path: String,
+    #[serde(default)]
+    pub edits: Vec<EditOperation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotebookEditInput {
+    pub notebook_path: String,
+    #[serde(default)]
+    pub new_source: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchInput {
+    pub pattern: String,
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BashInput {
+    pub command: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebFetchInput {
+    pub url: String,
+    #[serde(default)]
+    pub prompt: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebSearchInput {
+    pub query: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskInput {
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub prompt: String,
+    #[serde(default)]
+    pub subagent_type: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TodoStatus {
+    #[default]
+    Pending,
+    InProgress,
+    Completed,
+}
+
+/// One item of a TodoWrite plan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+pub struct TodoItem {
+    pub content: String,
+    #[serde(default)]
+    pub status: TodoStatus,
+    /// Present-tense label shown while the item is in progress
+    #[serde(default, rename = "activeForm")]
+    pub active_form: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TodoWriteInput {
+    pub todos: Vec<TodoItem>,
+}
+
+/// A tool_use `input` decoded by tool name.
+///
+/// Unknown tools (MCP, future tools) and inputs that don't match the expected
+/// shape are kept verbatim in `Other`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "tool", content = "input")]
+pub enum ToolInput {
+    Read(ReadInput),
+    Write(WriteInput),
+    Edit(EditInput),
+    MultiEdit(MultiEditInput),
+    NotebookEdit(NotebookEditInput),
+    Glob(SearchInput),
+    Grep(SearchInput),
+    Bash(BashInput),
+    WebFetch(WebFetchInput),
+    WebSearch(WebSearchInput),
+    Task(TaskInput),
+    TodoWrite(TodoWriteInput),
+    Other(Value),
+}
+
+/// How a tool call touched a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileAccess {
+    Read,
+    Edit,
+    Write,
+}
+
+impl ToolInput {
+    pub fn parse(tool_name: &str, input: &Value) -> Self {
+        fn typed<T: DeserializeOwned>(input: &Value, wrap: fn(T) -> ToolInput) -> ToolInput {
+            serde_json::from_value(input.clone())
+                .map(wrap)
+                .unwrap_or_else(|_| ToolInput::Other(input.clone()))
+        }
+
+        match tool_name {
+            "Read" => typed(input, ToolInput::Read),
+            "Write" => typed(input, ToolInput::Write),
+            "Edit" => typed(input, ToolInput::Edit),
+            "MultiEdit" => typed(input, ToolInput::MultiEdit),
+            "NotebookEdit" => typed(input, ToolInput::NotebookEdit),
+            "Glob" => typed(input, ToolInput::Glob),
+            "Grep" => typed(input, ToolInput::Grep),
+            "Bash" => typed(input, ToolInput::Bash),
+            "WebFetch" => typed(input, ToolInput::WebFetch),
+            "WebSearch" => typed(input, ToolInput::WebSearch),
+            "Task" => typed(input, ToolInput::Task),
+            "TodoWrite" => typed(input, ToolInput::TodoWrite),
+            _ => ToolInput::Other(input.clone()),
+        }
+    }
+
+    /// The file this call reads or changes, if any
+    pub fn file_access(&self) -> Option<(&str, FileAccess)> {
+        match self {
+            ToolInput::Read(i) => Some((&i.file_path, FileAccess::Read)),
+            ToolInput::Write(i) => Some((&i.file_path, FileAccess::Write)),
+            ToolInput::Edit(i) => Some((&i.file_path, FileAccess::Edit)),
+            ToolInput::MultiEdit(i) => Some((&i.file_path, FileAccess::Edit)),
+            ToolInput::NotebookEdit(i) => Some((&i.notebook_path, FileAccess::Edit)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_known_tools() {
+        let edit = ToolInput::parse(
+            "MultiEdit",
+            &json!({"file_path": "/a.rs", "edits": [{"old_string": "a", "new_string": "b"}]}),
+        );
+        assert_eq!(edit.file_access(), Some(("/a.rs", FileAccess::Edit)));
+
+        let bash = ToolInput::parse("Bash", &json!({"command": "ls", "timeout": 5}));
+        assert!(matches!(bash, ToolInput::Bash(BashInput { ref command, .. }) if command == "ls"));
+        assert_eq!(bash.file_access(), None);
+    }
+
+    #[test]
+    fn test_unexpected_shape_falls_back_to_other() {
+        let input = json!({"path": "/a.rs"});
+        assert_eq!(
+            ToolInput::parse("Read", &input),
+            ToolInput::Other(input.clone())
+        );
+        assert_eq!(
+            ToolInput::parse("mcp__github__create_issue", &input),
+            ToolInput::Other(input)
+        );
+    }
+}
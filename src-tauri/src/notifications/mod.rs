@@ -0,0 +1,5 @@
+mod notifier;
+mod triggers;
+
+pub use notifier::{NotificationCenter, TauriNotifier};
+pub use triggers::NotificationSettings;
@@ -0,0 +1,117 @@
+use crate::models::LogEntry;
+use crate::notifications::triggers::{Notification, NotificationSettings, TriggerEngine};
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_notification::NotificationExt;
+
+/// Delivers a notification to the user
+pub trait Notifier: Send + Sync {
+    fn notify(&self, notification: &Notification) -> Result<(), String>;
+}
+
+/// Native desktop notifications via the Tauri notification plugin
+pub struct TauriNotifier<R: Runtime> {
+    app: AppHandle<R>,
+}
+
+impl<R: Runtime> TauriNotifier<R> {
+    pub fn new(app: AppHandle<R>) -> Self {
+        Self { app }
+    }
+}
+
+impl<R: Runtime> Notifier for TauriNotifier<R> {
+    fn notify(&self, notification: &Notification) -> Result<(), String> {
+        self.app
+            .notification()
+            .builder()
+            .title(&notification.title)
+            .body(&notification.body)
+            .show()
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Shared entry point for the watcher (entries) and the ticker thread (timeouts)
+pub struct NotificationCenter {
+    notifier: Box<dyn Notifier>,
+    engine: Mutex<TriggerEngine>,
+}
+
+impl NotificationCenter {
+    pub fn new(notifier: Box<dyn Notifier>, settings: NotificationSettings) -> Self {
+        Self {
+            notifier,
+            engine: Mutex::new(TriggerEngine::new(settings)),
+        }
+    }
+
+    pub fn set_settings(&self, settings: NotificationSettings) {
+        self.lock().set_settings(settings);
+    }
+
+    pub fn observe(&self, entries: &[LogEntry]) {
+        let now = Instant::now();
+        let fired: Vec<Notification> = {
+            let mut engine = self.lock();
+            entries
+                .iter()
+                .flat_map(|entry| engine.observe(entry, now))
+                .collect()
+        };
+        self.deliver(&fired);
+    }
+
+    pub fn tick(&self) {
+        let fired = self.lock().tick(Instant::now());
+        self.deliver(&fired);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TriggerEngine> {
+        self.engine
+            .lock()
+            .expect("TriggerEngine mutex poisoned in notification center")
+    }
+
+    fn deliver(&self, notifications: &[Notification]) {
+        for notification in notifications {
+            if let Err(e) = self.notifier.notify(notification) {
+                tracing::warn!("Failed to show notification {:?}: {}", notification.kind, e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LogEntryType;
+    use crate::notifications::triggers::NotificationKind;
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct FakeNotifier {
+        sent: Arc<Mutex<Vec<Notification>>>,
+    }
+
+    impl Notifier for FakeNotifier {
+        fn notify(&self, notification: &Notification) -> Result<(), String> {
+            self.sent.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_center_delivers_to_notifier() {
+        let fake = FakeNotifier::default();
+        let sent = fake.sent.clone();
+        let center = NotificationCenter::new(Box::new(fake), NotificationSettings::default());
+
+        center.observe(&[LogEntry::test(LogEntryType::SessionEnd)]);
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].kind, NotificationKind::SessionFinished);
+    }
+}
@@ -0,0 +1,377 @@
+use crate::models::{LogEntry, LogEntryType};
+use crate::watcher::is_limit_reached_message;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+/// Claude Code phrases used when a tool call is waiting for user approval.
+static PERMISSION_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)needs your permission|permission to use|waiting for (your )?(approval|input)")
+        .expect("valid permission regex")
+});
+
+/// Minimum gap between two notifications of the same kind.
+const REPEAT_COOLDOWN: Duration = Duration::from_secs(10);
+
+/// Which events raise a desktop notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub enabled: bool,
+    /// Notify when a session ends or goes quiet for `session_idle_secs`
+    pub session_finished: bool,
+    pub session_idle_secs: u64,
+    /// Notify when `error_burst_count` errors arrive within `error_burst_window_secs`
+    pub error_burst: bool,
+    pub error_burst_count: usize,
+    pub error_burst_window_secs: u64,
+    pub rate_limit: bool,
+    pub permission_needed: bool,
+    /// Notify when a tool call has no result after `long_running_tool_secs`
+    pub long_running_tool: bool,
+    pub long_running_tool_secs: u64,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            session_finished: true,
+            session_idle_secs: 30,
+            error_burst: true,
+            error_burst_count: 3,
+            error_burst_window_secs: 60,
+            rate_limit: true,
+            permission_needed: true,
+            long_running_tool: false,
+            long_running_tool_secs: 120,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    SessionFinished,
+    ErrorBurst,
+    RateLimit,
+    PermissionNeeded,
+    LongRunningTool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub title: String,
+    pub body: String,
+}
+
+impl Notification {
+    fn new(kind: NotificationKind, title: &str, body: impl Into<String>) -> Self {
+        Self {
+            kind,
+            title: title.to_string(),
+            body: body.into(),
+        }
+    }
+}
+
+/// Pending tool call waiting for its result
+struct RunningTool {
+    name: String,
+    started_at: Instant,
+    notified: bool,
+}
+
+/// Turns the parsed entry stream into notifications.
+///
+/// Time is passed in explicitly so the rules can be driven deterministically in tests.
+pub struct TriggerEngine {
+    settings: NotificationSettings,
+    recent_errors: VecDeque<Instant>,
+    /// Keyed by `tool_use_id`, or the tool name when the log has no id
+    running_tools: HashMap<String, RunningTool>,
+    last_activity: Option<Instant>,
+    rate_limited: bool,
+    last_fired: HashMap<NotificationKind, Instant>,
+}
+
+impl TriggerEngine {
+    pub fn new(settings: NotificationSettings) -> Self {
+        Self {
+            settings,
+            recent_errors: VecDeque::new(),
+            running_tools: HashMap::new(),
+            last_activity: None,
+            rate_limited: false,
+            last_fired: HashMap::new(),
+        }
+    }
+
+    pub fn set_settings(&mut self, settings: NotificationSettings) {
+        self.settings = settings;
+    }
+
+    pub fn observe(&mut self, entry: &LogEntry, now: Instant) -> Vec<Notification> {
+        let mut out = Vec::new();
+        self.last_activity = Some(now);
+
+        match entry.entry_type {
+            LogEntryType::ToolCall => {
+                if let Some(name) = &entry.tool_name {
+                    let key = entry.tool_use_id.clone().unwrap_or_else(|| name.clone());
+                    self.running_tools.insert(
+                        key,
+                        RunningTool {
+                            name: name.clone(),
+                            started_at: now,
+                            notified: false,
+                        },
+                    );
+                }
+            }
+            LogEntryType::ToolResult => match (&entry.tool_use_id, &entry.tool_name) {
+                (Some(id), _) => {
+                    self.running_tools.remove(id);
+                }
+                (None, Some(name)) => self.running_tools.retain(|_, tool| &tool.name != name),
+                // A result with neither answers the pending call(s).
+                (None, None) => self.running_tools.clear(),
+            },
+            LogEntryType::SessionEnd => {
+                self.last_activity = None;
+                self.running_tools.clear();
+                if self.settings.session_finished {
+                    out.push(Notification::new(
+                        NotificationKind::SessionFinished,
+                        "Session finished",
+                        "Claude has finished the session.",
+                    ));
+                }
+            }
+            _ => {}
+        }
+
+        if entry.entry_type == LogEntryType::Error && self.settings.error_burst {
+            let window = Duration::from_secs(self.settings.error_burst_window_secs);
+            self.recent_errors.push_back(now);
+            while self
+                .recent_errors
+                .front()
+                .is_some_and(|t| now.duration_since(*t) > window)
+            {
+                self.recent_errors.pop_front();
+            }
+            if self.recent_errors.len() >= self.settings.error_burst_count.max(1) {
+                let count = self.recent_errors.len();
+                self.recent_errors.clear();
+                out.push(Notification::new(
+                    NotificationKind::ErrorBurst,
+                    "Errors piling up",
+                    format!(
+                        "{count} errors in the last {}s.",
+                        self.settings.error_burst_window_secs
+                    ),
+                ));
+            }
+        }
+
+        if is_limit_reached_message(&entry.content) {
+            if !self.rate_limited && self.settings.rate_limit {
+                out.push(Notification::new(
+                    NotificationKind::RateLimit,
+                    "Rate limit hit",
+                    "Claude is waiting for the usage limit to reset.",
+                ));
+            }
+            self.rate_limited = true;
+        } else if matches!(
+            entry.entry_type,
+            LogEntryType::ToolCall | LogEntryType::ToolResult
+        ) {
+            self.rate_limited = false;
+        }
+
+        if self.settings.permission_needed && PERMISSION_PATTERN.is_match(&entry.content) {
+            out.push(Notification::new(
+                NotificationKind::PermissionNeeded,
+                "Permission needed",
+                entry.content.chars().take(120).collect::<String>(),
+            ));
+        }
+
+        self.finish(out, now)
+    }
+
+    /// Evaluate time-based rules (idle session, long-running tools).
+    pub fn tick(&mut self, now: Instant) -> Vec<Notification> {
+        let mut out = Vec::new();
+
+        if self.settings.long_running_tool {
+            let limit = Duration::from_secs(self.settings.long_running_tool_secs);
+            for tool in self.running_tools.values_mut() {
+                if !tool.notified && now.duration_since(tool.started_at) >= limit {
+                    tool.notified = true;
+                    out.push(Notification::new(
+                        NotificationKind::LongRunningTool,
+                        "Tool still running",
+                        format!(
+                            "{} has been running for over {}s.",
+                            tool.name,
+                            limit.as_secs()
+                        ),
+                    ));
+                }
+            }
+        }
+
+        let idle = Duration::from_secs(self.settings.session_idle_secs);
+        if let Some(last) = self.last_activity {
+            if self.running_tools.is_empty() && now.duration_since(last) >= idle {
+                self.last_activity = None;
+                if self.settings.session_finished {
+                    out.push(Notification::new(
+                        NotificationKind::SessionFinished,
+                        "Claude stopped",
+                        format!("No activity for {}s.", idle.as_secs()),
+                    ));
+                }
+            }
+        }
+
+        self.finish(out, now)
+    }
+
+    /// Drop everything when disabled and rate-limit repeats of the same kind.
+    fn finish(&mut self, out: Vec<Notification>, now: Instant) -> Vec<Notification> {
+        if !self.settings.enabled {
+            return Vec::new();
+        }
+        out.into_iter()
+            .filter(|n| {
+                let recent = self
+                    .last_fired
+                    .get(&n.kind)
+                    .is_some_and(|t| now.duration_since(*t) < REPEAT_COOLDOWN);
+                if !recent {
+                    self.last_fired.insert(n.kind, now);
+                }
+                !recent
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(notifications: &[Notification]) -> Vec<NotificationKind> {
+        notifications.iter().map(|n| n.kind).collect()
+    }
+
+    #[test]
+    fn test_error_burst_fires_at_threshold() {
+        let mut engine = TriggerEngine::new(NotificationSettings::default());
+        let t0 = Instant::now();
+        let error = LogEntry::test(LogEntryType::Error).content("Error: boom");
+
+        assert!(engine.observe(&error, t0).is_empty());
+        assert!(engine
+            .observe(&error, t0 + Duration::from_secs(1))
+            .is_empty());
+        let fired = engine.observe(&error, t0 + Duration::from_secs(2));
+        assert_eq!(kinds(&fired), vec![NotificationKind::ErrorBurst]);
+    }
+
+    #[test]
+    fn test_rate_limit_latches_until_activity() {
+        let mut engine = TriggerEngine::new(NotificationSettings::default());
+        let t0 = Instant::now();
+        let limited = LogEntry::test(LogEntryType::Message).content("Claude usage limit reached");
+
+        assert_eq!(
+            kinds(&engine.observe(&limited, t0)),
+            vec![NotificationKind::RateLimit]
+        );
+        assert!(engine
+            .observe(&limited, t0 + Duration::from_secs(30))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_long_running_tool_and_idle_session() {
+        let settings = NotificationSettings {
+            long_running_tool: true,
+            long_running_tool_secs: 5,
+            session_idle_secs: 20,
+            ..Default::default()
+        };
+        let mut engine = TriggerEngine::new(settings);
+        let t0 = Instant::now();
+
+        engine.observe(
+            &LogEntry::test(LogEntryType::ToolCall)
+                .tool("Bash")
+                .content("cargo build"),
+            t0,
+        );
+        assert!(engine.tick(t0 + Duration::from_secs(2)).is_empty());
+        assert_eq!(
+            kinds(&engine.tick(t0 + Duration::from_secs(6))),
+            vec![NotificationKind::LongRunningTool]
+        );
+
+        let t1 = t0 + Duration::from_secs(7);
+        engine.observe(&LogEntry::test(LogEntryType::ToolResult).content("ok"), t1);
+        assert!(engine.tick(t1 + Duration::from_secs(10)).is_empty());
+        assert_eq!(
+            kinds(&engine.tick(t1 + Duration::from_secs(21))),
+            vec![NotificationKind::SessionFinished]
+        );
+    }
+
+    #[test]
+    fn test_running_tools_keyed_by_tool_use_id() {
+        let settings = NotificationSettings {
+            long_running_tool: true,
+            long_running_tool_secs: 5,
+            ..Default::default()
+        };
+        let mut engine = TriggerEngine::new(settings);
+        let t0 = Instant::now();
+        let call = |id: &str| {
+            LogEntry::test(LogEntryType::ToolCall)
+                .tool("Bash")
+                .content("sleep 60")
+                .tool_use_id(id)
+        };
+
+        // Two parallel Bash calls; finishing one leaves the other running.
+        engine.observe(&call("toolu_a"), t0);
+        engine.observe(&call("toolu_b"), t0);
+        let result = LogEntry::test(LogEntryType::ToolResult)
+            .content("ok")
+            .tool_use_id("toolu_a");
+        engine.observe(&result, t0 + Duration::from_secs(1));
+        let fired = engine.tick(t0 + Duration::from_secs(6));
+        assert_eq!(kinds(&fired), vec![NotificationKind::LongRunningTool]);
+    }
+
+    #[test]
+    fn test_disabled_suppresses_everything() {
+        let settings = NotificationSettings {
+            enabled: false,
+            ..Default::default()
+        };
+        let mut engine = TriggerEngine::new(settings);
+        let fired = engine.observe(
+            &LogEntry::test(LogEntryType::Message)
+                .content("Claude needs your permission to use Bash"),
+            Instant::now(),
+        );
+        assert!(fired.is_empty());
+    }
+}
@@ -0,0 +1,512 @@
+use crate::events::{HudCounters, WatcherInfo};
+use crate::models::{
+    Agent, AgentState, AgentStatus, AgentType, Handoff, HandoffReason, LogEntry, LogEntryType,
+    TodoStatus, ToolInput,
+};
+use crate::watcher::{
+    agent_id_for_type, determine_agent_status, determine_agent_type, is_limit_reached_message,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Hand-off tracking for a session with no entries for this long is dropped
+const SESSION_LINKS_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Timed agent transitions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OfficeSettings {
+    /// A working agent with no new entries for this long goes idle
+    pub working_timeout_secs: u64,
+    /// Same for a thinking agent (assistant text without a tool call)
+    pub thinking_timeout_secs: u64,
+    /// An agent in error goes back to idle after this long
+    pub error_decay_secs: u64,
+    /// How long an agent shows "passing" after handing work to another agent
+    pub passing_secs: u64,
+}
+
+impl Default for OfficeSettings {
+    fn default() -> Self {
+        Self {
+            working_timeout_secs: 60,
+            thinking_timeout_secs: 30,
+            error_decay_secs: 15,
+            passing_secs: 2,
+        }
+    }
+}
+
+/// An agent's state plus the ingest times its timeouts run on
+struct TrackedAgent {
+    state: AgentState,
+    last_activity_at: Option<Instant>,
+    status_since: Option<Instant>,
+}
+
+impl TrackedAgent {
+    fn new(agent_type: AgentType) -> Self {
+        Self {
+            state: AgentState::new(Agent::new(
+                agent_id_for_type(agent_type).to_string(),
+                agent_type,
+                get_desk_position(agent_type),
+            )),
+            last_activity_at: None,
+            status_since: None,
+        }
+    }
+
+    fn set_status(&mut self, status: AgentStatus, now: Instant) {
+        if self.state.agent.status != status {
+            self.state.agent.status = status;
+            self.status_since = Some(now);
+        }
+    }
+}
+
+/// Everything a freshly loaded webview needs to catch up
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(test, derive(ts_rs::TS, schemars::JsonSchema))]
+pub struct OfficeSnapshot {
+    /// Sequence number of the last event reflected here
+    #[cfg_attr(test, ts(type = "number"))]
+    pub seq: u64,
+    /// Agents seen so far, including idle ones
+    pub agents: Vec<AgentState>,
+    /// Recent log entries, oldest first
+    pub logs: Vec<LogEntry>,
+    /// Active session ids, most recent first
+    pub sessions: Vec<String>,
+    pub watcher: Option<WatcherInfo>,
+    pub hud: HudCounters,
+}
+
+/// Result of applying a batch of entries
+#[derive(Debug, Default)]
+pub struct OfficeChanges {
+    /// Agents whose state changed
+    pub agents: Vec<AgentState>,
+    pub handoffs: Vec<Handoff>,
+}
+
+/// What a session's agents touched, for detecting hand-offs within that session
+struct SessionLinks {
+    /// Agent that last touched each file path
+    file_owners: HashMap<String, String>,
+    /// Agent that issued a Task call, and its description, until another agent picks it up
+    pending_delegation: Option<(String, Option<String>)>,
+    last_seen: Instant,
+}
+
+impl SessionLinks {
+    fn new(now: Instant) -> Self {
+        Self {
+            file_owners: HashMap::new(),
+            pending_delegation: None,
+            last_seen: now,
+        }
+    }
+}
+
+/// Backend-owned agent state machine.
+///
+/// `apply` and `tick` return only the agents whose state actually changed.
+pub struct OfficeState {
+    settings: OfficeSettings,
+    agents: HashMap<String, TrackedAgent>,
+    /// Keyed by session id; entries without one share the empty key
+    sessions: HashMap<String, SessionLinks>,
+}
+
+impl OfficeState {
+    pub fn new(settings: OfficeSettings) -> Self {
+        Self {
+            settings,
+            agents: HashMap::new(),
+            sessions: HashMap::new(),
+        }
+    }
+
+    pub fn set_settings(&mut self, settings: OfficeSettings) {
+        self.settings = settings;
+    }
+
+    pub fn apply(&mut self, entries: &[LogEntry], now: Instant) -> OfficeChanges {
+        let before = self.visible_state();
+        let mut handoffs = Vec::new();
+        for entry in entries {
+            handoffs.extend(self.apply_entry(entry, now));
+        }
+        OfficeChanges {
+            agents: self.changed_since(&before),
+            handoffs,
+        }
+    }
+
+    fn apply_entry(&mut self, entry: &LogEntry, now: Instant) -> Option<Handoff> {
+        let agent_type = determine_agent_type(entry);
+        let id = agent_id_for_type(agent_type).to_string();
+
+        let session = entry.session_id.clone().unwrap_or_default();
+        let handoff = match entry.entry_type {
+            LogEntryType::SessionEnd => {
+                self.sessions.remove(&session);
+                None
+            }
+            LogEntryType::ToolCall => {
+                let links = self
+                    .sessions
+                    .entry(session)
+                    .or_insert_with(|| SessionLinks::new(now));
+                links.last_seen = now;
+                detect_handoff(links, entry, &id)
+            }
+            _ => {
+                if let Some(links) = self.sessions.get_mut(&session) {
+                    links.last_seen = now;
+                }
+                None
+            }
+        };
+        if let Some(from) = handoff.as_ref().and_then(|h| self.agents.get_mut(&h.from)) {
+            if from.state.agent.status != AgentStatus::Error {
+                from.set_status(AgentStatus::Passing, now);
+            }
+        }
+
+        let tracked = self
+            .agents
+            .entry(id)
+            .or_insert_with(|| TrackedAgent::new(agent_type));
+        let state = &mut tracked.state;
+
+        match entry.entry_type {
+            LogEntryType::Error => {
+                state.error_count += 1;
+                state.last_error = Some(entry.timestamp);
+            }
+            LogEntryType::ToolCall | LogEntryType::ToolResult => {
+                state.error_count = 0;
+                state.rate_limited = false;
+                if entry.entry_type == LogEntryType::ToolCall {
+                    state.last_tool_call = Some(entry.timestamp);
+                }
+            }
+            _ => {}
+        }
+        if is_limit_reached_message(&entry.content) {
+            state.rate_limited = true;
+        }
+
+        state.agent.current_task = Some(summarize_current_task(entry));
+        state.last_activity = Some(entry.timestamp);
+        tracked.set_status(determine_agent_status(entry), now);
+        tracked.last_activity_at = Some(now);
+        handoff
+    }
+
+    /// Apply timeouts: idle after inactivity, error decay, end of hand-off.
+    pub fn tick(&mut self, now: Instant) -> Vec<AgentState> {
+        self.sessions
+            .retain(|_, links| now.duration_since(links.last_seen) < SESSION_LINKS_TTL);
+        let before = self.visible_state();
+        let secs = Duration::from_secs;
+        for tracked in self.agents.values_mut() {
+            let since_activity = tracked.last_activity_at.map(|t| now.duration_since(t));
+            let since_status = tracked.status_since.map(|t| now.duration_since(t));
+            let expired = match tracked.state.agent.status {
+                AgentStatus::Working => {
+                    since_activity >= Some(secs(self.settings.working_timeout_secs))
+                }
+                AgentStatus::Thinking => {
+                    since_activity >= Some(secs(self.settings.thinking_timeout_secs))
+                }
+                AgentStatus::Error => since_status >= Some(secs(self.settings.error_decay_secs)),
+                AgentStatus::Passing => since_status >= Some(secs(self.settings.passing_secs)),
+                AgentStatus::Idle => false,
+            };
+            if expired {
+                if tracked.state.agent.status == AgentStatus::Error {
+                    tracked.state.error_count = 0;
+                }
+                tracked.set_status(AgentStatus::Idle, now);
+                tracked.state.agent.current_task = None;
+            }
+        }
+        self.changed_since(&before)
+    }
+
+    /// Every agent seen so far, ordered by id.
+    pub fn agents(&self) -> Vec<AgentState> {
+        let mut agents: Vec<AgentState> = self.agents.values().map(|t| t.state.clone()).collect();
+        agents.sort_by(|a, b| a.agent.id.cmp(&b.agent.id));
+        agents
+    }
+
+    fn visible_state(&self) -> HashMap<String, AgentState> {
+        self.agents
+            .iter()
+            .map(|(id, t)| (id.clone(), t.state.clone()))
+            .collect()
+    }
+
+    fn changed_since(&self, before: &HashMap<String, AgentState>) -> Vec<AgentState> {
+        let mut changed: Vec<AgentState> = self
+            .agents
+            .iter()
+            .filter(|(id, t)| before.get(*id) != Some(&t.state))
+            .map(|(_, t)| t.state.clone())
+            .collect();
+        changed.sort_by(|a, b| a.agent.id.cmp(&b.agent.id));
+        changed
+    }
+}
+
+/// A tool call by `to` that picks up a delegated task or a file another agent touched
+/// in the same session.
+fn detect_handoff(links: &mut SessionLinks, entry: &LogEntry, to: &str) -> Option<Handoff> {
+    let handoff = |from: String, reason, artifact| Handoff {
+        from,
+        to: to.to_string(),
+        reason,
+        artifact,
+        tool_name: entry.tool_name.clone(),
+    };
+
+    if let Some(ToolInput::Task(task)) = &entry.tool_input {
+        let description = Some(task.description.clone())
+            .filter(|d| !d.is_empty())
+            .or_else(|| task.subagent_type.clone());
+        links.pending_delegation = Some((to.to_string(), description));
+        return None;
+    }
+    let shared_file = entry
+        .tool_input
+        .as_ref()
+        .and_then(ToolInput::file_access)
+        .and_then(|(path, _)| {
+            let previous = links.file_owners.insert(path.to_string(), to.to_string())?;
+            (previous != to).then(|| (previous, path.to_string()))
+        });
+    if let Some((from, artifact)) = links.pending_delegation.take_if(|(from, _)| from != to) {
+        return Some(handoff(from, HandoffReason::Delegation, artifact));
+    }
+    shared_file.map(|(from, path)| handoff(from, HandoffReason::SharedFile, Some(path)))
+}
+
+fn summarize_current_task(entry: &LogEntry) -> String {
+    // Show the step the plan is on rather than the bare tool name.
+    if let Some(ToolInput::TodoWrite(plan)) = &entry.tool_input {
+        if let Some(step) = plan
+            .todos
+            .iter()
+            .find(|t| t.status == TodoStatus::InProgress)
+        {
+            return format!("Todo: {}", step.active_form)
+                .chars()
+                .take(200)
+                .collect();
+        }
+    }
+
+    let summary: String = match entry.entry_type {
+        LogEntryType::ToolCall | LogEntryType::ToolResult => {
+            let prefix = if entry.entry_type == LogEntryType::ToolCall {
+                "Tool call"
+            } else {
+                "Tool result"
+            };
+            entry
+                .tool_name
+                .as_deref()
+                .map_or_else(|| prefix.to_string(), |name| format!("{prefix}: {name}"))
+        }
+        LogEntryType::TodoUpdate => "Todo update".to_string(),
+        LogEntryType::SessionStart => "Session start".to_string(),
+        LogEntryType::SessionEnd => "Session end".to_string(),
+        LogEntryType::Error => "Error".to_string(),
+        // Avoid cloning the full message; keep only a short preview.
+        LogEntryType::Message => entry.content.chars().take(200).collect(),
+    };
+
+    // Hard cap to keep payload small even if other branches produce longer strings.
+    summary.chars().take(200).collect()
+}
+
+/// Desk positions matching TypeScript DESK_CONFIGS (workflow-based).
+/// Note: These values are currently unused by frontend (which uses its own DESK_CONFIGS),
+/// but kept for API consistency.
+fn get_desk_position(agent_type: AgentType) -> (f32, f32) {
+    // Layout: 3-3-2 vertical arrangement (workflow-based)
+    // Section A (Y=130): Explorer, Analyzer, Architect
+    // Section B (Y=320): Developer, Operator, Validator
+    // Section C (Y=520): Connector, Liaison
+    match agent_type {
+        AgentType::Explorer => (60.0, 130.0),
+        AgentType::Analyzer => (150.0, 130.0),
+        AgentType::Architect => (240.0, 130.0),
+        AgentType::Developer => (60.0, 320.0),
+        AgentType::Operator => (150.0, 320.0),
+        AgentType::Validator => (240.0, 320.0),
+        AgentType::Connector => (60.0, 520.0),
+        AgentType::Liaison => (150.0, 520.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(tool: &str, input: serde_json::Value) -> LogEntry {
+        LogEntry::test(LogEntryType::ToolCall)
+            .tool(tool)
+            .input(input)
+    }
+
+    #[test]
+    fn test_emits_only_real_changes() {
+        let mut office = OfficeState::new(OfficeSettings::default());
+        let t0 = Instant::now();
+
+        let changed = office.apply(&[LogEntry::test(LogEntryType::ToolCall).tool("Edit")], t0);
+        assert_eq!(changed.agents.len(), 1);
+        assert_eq!(
+            (
+                changed.agents[0].agent.id.as_str(),
+                changed.agents[0].agent.status
+            ),
+            ("developer", AgentStatus::Working)
+        );
+        assert!(office
+            .apply(&[LogEntry::test(LogEntryType::ToolCall).tool("Edit")], t0)
+            .agents
+            .is_empty());
+
+        // Switching agents without a shared artifact is not a hand-off.
+        let changed = office.apply(&[LogEntry::test(LogEntryType::ToolCall).tool("Read")], t0);
+        assert_eq!(changed.agents.len(), 1);
+        assert!(changed.handoffs.is_empty());
+    }
+
+    #[test]
+    fn test_detects_shared_file_and_delegation_handoffs() {
+        let mut office = OfficeState::new(OfficeSettings::default());
+        let t0 = Instant::now();
+        let file = serde_json::json!({"file_path": "/repo/src/lib.rs"});
+
+        let changed = office.apply(
+            &[
+                call("Read", file.clone()),
+                call("Edit", serde_json::json!({"file_path": "/repo/src/lib.rs", "old_string": "a", "new_string": "b"})),
+            ],
+            t0,
+        );
+        assert_eq!(
+            changed.handoffs,
+            vec![Handoff {
+                from: "explorer".to_string(),
+                to: "developer".to_string(),
+                reason: HandoffReason::SharedFile,
+                artifact: Some("/repo/src/lib.rs".to_string()),
+                tool_name: Some("Edit".to_string()),
+            }]
+        );
+        let explorer = changed
+            .agents
+            .iter()
+            .find(|s| s.agent.id == "explorer")
+            .unwrap();
+        assert_eq!(explorer.agent.status, AgentStatus::Passing);
+
+        let changed = office.apply(
+            &[
+                call(
+                    "Task",
+                    serde_json::json!({"description": "Run the tests", "prompt": "..."}),
+                ),
+                call("Bash", serde_json::json!({"command": "ls"})),
+            ],
+            t0,
+        );
+        assert_eq!(changed.handoffs.len(), 1);
+        assert_eq!(
+            (
+                changed.handoffs[0].from.as_str(),
+                changed.handoffs[0].reason,
+                changed.handoffs[0].artifact.as_deref()
+            ),
+            (
+                "architect",
+                HandoffReason::Delegation,
+                Some("Run the tests")
+            )
+        );
+
+        let ended = office.tick(t0 + Duration::from_secs(2));
+        assert!(ended
+            .iter()
+            .any(|s| s.agent.id == "architect" && s.agent.status == AgentStatus::Idle));
+    }
+
+    #[test]
+    fn test_handoffs_stay_within_a_session() {
+        let mut office = OfficeState::new(OfficeSettings::default());
+        let t0 = Instant::now();
+        let in_session = |session: &str, tool: &str, input: serde_json::Value| {
+            call(tool, input).session(session)
+        };
+        let read = serde_json::json!({"file_path": "/repo/src/lib.rs"});
+        let edit = serde_json::json!({"file_path": "/repo/src/lib.rs", "old_string": "a", "new_string": "b"});
+
+        // The same file in another session is not a hand-off.
+        office.apply(&[in_session("s1", "Read", read.clone())], t0);
+        let changed = office.apply(&[in_session("s2", "Edit", edit.clone())], t0);
+        assert!(changed.handoffs.is_empty());
+
+        // Ending the session forgets who touched what.
+        let end = LogEntry::test(LogEntryType::SessionEnd).session("s1");
+        office.apply(&[end], t0);
+        let changed = office.apply(&[in_session("s1", "Edit", edit.clone())], t0);
+        assert!(changed.handoffs.is_empty());
+
+        // So does a session that went quiet.
+        office.apply(&[in_session("s3", "Read", read)], t0);
+        office.tick(t0 + SESSION_LINKS_TTL);
+        let changed = office.apply(&[in_session("s3", "Edit", edit)], t0 + SESSION_LINKS_TTL);
+        assert!(changed.handoffs.is_empty());
+    }
+
+    #[test]
+    fn test_timeouts_and_error_decay() {
+        let mut office = OfficeState::new(OfficeSettings::default());
+        let t0 = Instant::now();
+        office.apply(
+            &[
+                LogEntry::test(LogEntryType::ToolCall).tool("Bash"),
+                LogEntry::test(LogEntryType::Error).tool("Bash"),
+            ],
+            t0,
+        );
+        let operator = |office: &OfficeState| {
+            office
+                .agents()
+                .into_iter()
+                .find(|s| s.agent.id == "operator")
+                .unwrap()
+        };
+        assert_eq!(operator(&office).error_count, 1);
+
+        assert!(office.tick(t0 + Duration::from_secs(5)).is_empty());
+        let decayed = office.tick(t0 + Duration::from_secs(15));
+        // The decay is emitted with the reset error count, so clients need no timer of their own.
+        assert_eq!(decayed[0].agent.status, AgentStatus::Idle);
+        assert_eq!(decayed[0].agent.current_task, None);
+        assert_eq!(decayed[0].error_count, 0);
+
+        office.apply(&[LogEntry::test(LogEntryType::ToolCall).tool("Bash")], t0);
+        let idle = office.tick(t0 + Duration::from_secs(60));
+        assert_eq!(idle[0].agent.status, AgentStatus::Idle);
+    }
+}
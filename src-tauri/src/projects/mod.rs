@@ -0,0 +1,267 @@
+use crate::history;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+
+/// Lines read from the top of a transcript when looking for `cwd`/`gitBranch`
+const HEAD_LINES: usize = 20;
+
+/// Which `~/.claude/projects` folders the watcher follows
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectSettings {
+    /// Encoded project folder names (see `ProjectInfo::id`); empty watches everything
+    pub watched: Vec<String>,
+}
+
+impl ProjectSettings {
+    pub fn is_watched(&self, project_id: &str) -> bool {
+        self.watched.is_empty() || self.watched.iter().any(|p| p == project_id)
+    }
+}
+
+/// A repository Claude Code has been run in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectInfo {
+    /// Folder name under `~/.claude/projects`, e.g. `-Users-me-app`
+    pub id: String,
+    /// Decoded repository path, e.g. `/Users/me/app`
+    pub path: String,
+    /// Last path component
+    pub name: String,
+    /// Newest session first
+    pub sessions: Vec<SessionInfo>,
+    pub watched: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub session_id: String,
+    pub cwd: Option<String>,
+    pub git_branch: Option<String>,
+    /// Transcript modification time (RFC 3339)
+    pub last_active: Option<String>,
+}
+
+/// Every project under `root`, most recently active first.
+pub fn list_projects(root: &Path, settings: &ProjectSettings) -> Vec<ProjectInfo> {
+    let mut by_project: HashMap<String, Vec<(PathBuf, Option<std::time::SystemTime>)>> =
+        HashMap::new();
+    for file in history::list_session_files(root) {
+        let modified = file.metadata().and_then(|m| m.modified()).ok();
+        by_project
+            .entry(history::project_name(&file))
+            .or_default()
+            .push((file, modified));
+    }
+
+    let mut projects: Vec<(Option<std::time::SystemTime>, ProjectInfo)> = by_project
+        .into_iter()
+        .map(|(id, mut files)| {
+            files.sort_by_key(|f| std::cmp::Reverse(f.1));
+            let sessions: Vec<SessionInfo> = files
+                .iter()
+                .map(|(file, modified)| session_info(file, *modified))
+                .collect();
+            let path = decode_project_dir(&id);
+            let info = ProjectInfo {
+                name: project_display_name(&path),
+                watched: settings.is_watched(&id),
+                id,
+                path,
+                sessions,
+            };
+            (files.first().and_then(|f| f.1), info)
+        })
+        .collect();
+
+    projects.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.id.cmp(&b.1.id)));
+    projects.into_iter().map(|(_, info)| info).collect()
+}
+
+fn session_info(file: &Path, modified: Option<std::time::SystemTime>) -> SessionInfo {
+    let mut info = SessionInfo {
+        session_id: file
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        cwd: None,
+        git_branch: None,
+        last_active: modified.map(|m| chrono::DateTime::<chrono::Utc>::from(m).to_rfc3339()),
+    };
+
+    let Ok(file) = std::fs::File::open(file) else {
+        return info;
+    };
+    for line in BufReader::new(file)
+        .lines()
+        .take(HEAD_LINES)
+        .map_while(Result::ok)
+    {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(&line) else {
+            continue;
+        };
+        let field = |key: &str| json.get(key).and_then(|v| v.as_str()).map(String::from);
+        info.cwd = info.cwd.or_else(|| field("cwd"));
+        info.git_branch = info.git_branch.or_else(|| field("gitBranch"));
+        if info.cwd.is_some() && info.git_branch.is_some() {
+            break;
+        }
+    }
+    info
+}
+
+/// Project folder id for a file under `projects_dir`, if it is a session transcript.
+pub fn project_id_for(projects_dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(projects_dir).ok()?;
+    relative
+        .components()
+        .next()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+}
+
+pub fn project_display_name(path: &str) -> String {
+    path.rsplit(['/', '\\'])
+        .find(|s| !s.is_empty())
+        .unwrap_or(path)
+        .to_string()
+}
+
+static DECODED: LazyLock<Mutex<HashMap<String, String>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Decode a `~/.claude/projects` folder name back into the repository path.
+///
+/// Claude Code replaces every non-alphanumeric path character with `-`, so
+/// `-Users-me-my-app` could be `/Users/me/my-app` or `/Users/me/my/app`. The
+/// real directories on disk disambiguate; if none match, dashes become slashes.
+/// Windows folders start with the drive letter: `C--Users-me-app` is `C:\Users\me\app`.
+pub fn decode_project_dir(encoded: &str) -> String {
+    if let Some(path) = DECODED
+        .lock()
+        .expect("project decode cache poisoned")
+        .get(encoded)
+    {
+        return path.clone();
+    }
+
+    let path = match split_drive(encoded) {
+        Some((drive, rest)) => {
+            let root = format!("{drive}:\\");
+            resolve(Path::new(&root), rest)
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_else(|| format!("{root}{}", rest.replace('-', "\\")))
+        }
+        None => encoded
+            .strip_prefix('-')
+            .and_then(|rest| resolve(Path::new("/"), rest))
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| encoded.replace('-', "/")),
+    };
+
+    DECODED
+        .lock()
+        .expect("project decode cache poisoned")
+        .insert(encoded.to_string(), path.clone());
+    path
+}
+
+/// Find the existing path under `dir` whose encoding is `rest`, preferring longer names.
+fn resolve(dir: &Path, rest: &str) -> Option<PathBuf> {
+    if rest.is_empty() {
+        return Some(dir.to_path_buf());
+    }
+
+    let mut candidates: Vec<(usize, String)> = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|child| child.path().is_dir())
+        .filter_map(|child| {
+            let name = child.file_name().to_string_lossy().to_string();
+            let encoded = encode_component(&name);
+            let matches = rest.starts_with(&encoded)
+                && matches!(rest.as_bytes().get(encoded.len()), None | Some(b'-'));
+            matches.then_some((encoded.len(), name))
+        })
+        .collect();
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.0));
+
+    candidates
+        .into_iter()
+        .find_map(|(len, name)| resolve(&dir.join(name), rest.get(len + 1..).unwrap_or("")))
+}
+
+/// `C--Users-me` -> (`C`, `Users-me`); `:` and `\` both encode to `-`.
+fn split_drive(encoded: &str) -> Option<(char, &str)> {
+    let mut chars = encoded.chars();
+    let drive = chars.next().filter(char::is_ascii_alphabetic)?;
+    Some((drive, chars.as_str().strip_prefix("--")?))
+}
+
+fn encode_component(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefers_existing_directories() {
+        let root =
+            std::env::temp_dir().join(format!("agents-office-projects-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("my-app").join("src")).unwrap();
+        std::fs::create_dir_all(root.join("my").join("lib")).unwrap();
+        std::fs::create_dir_all(root.join(".config")).unwrap();
+
+        assert_eq!(
+            resolve(&root, "my-app-src"),
+            Some(root.join("my-app").join("src"))
+        );
+        assert_eq!(resolve(&root, "my-lib"), Some(root.join("my").join("lib")));
+        assert_eq!(resolve(&root, "-config"), Some(root.join(".config")));
+        assert_eq!(resolve(&root, "missing"), None);
+
+        assert_eq!(
+            decode_project_dir("Q--nonexistent-agents-office"),
+            "Q:\\nonexistent\\agents\\office"
+        );
+        assert_eq!(decode_project_dir("-nonexistent-app"), "/nonexistent/app");
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_list_projects_reads_session_metadata() {
+        let root =
+            std::env::temp_dir().join(format!("agents-office-project-list-{}", std::process::id()));
+        let project = root.join("-nonexistent-repo");
+        std::fs::create_dir_all(&project).unwrap();
+        std::fs::write(
+            project.join("s1.jsonl"),
+            r#"{"type":"summary"}
+{"cwd":"/nonexistent/repo","gitBranch":"main","sessionId":"s1"}"#,
+        )
+        .unwrap();
+
+        let settings = ProjectSettings {
+            watched: vec!["-other".to_string()],
+        };
+        let projects = list_projects(&root, &settings);
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].path, "/nonexistent/repo");
+        assert_eq!(projects[0].name, "repo");
+        assert!(!projects[0].watched);
+        assert_eq!(projects[0].sessions[0].git_branch.as_deref(), Some("main"));
+        assert_eq!(
+            project_id_for(&root, &project.join("s1.jsonl")).as_deref(),
+            Some("-nonexistent-repo")
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
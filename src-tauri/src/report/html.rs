@@ -0,0 +1,186 @@
+use crate::models::{AgentType, LogEntryType, TodoStatus};
+use crate::report::SessionReport;
+use crate::watcher::agent_id_for_type;
+use std::fmt::Write;
+
+/// Lane order matches the office sections (A: explore/design, B: build/verify, C: integrate)
+const LANES: [AgentType; 8] = [
+    AgentType::Explorer,
+    AgentType::Analyzer,
+    AgentType::Architect,
+    AgentType::Developer,
+    AgentType::Operator,
+    AgentType::Validator,
+    AgentType::Connector,
+    AgentType::Liaison,
+];
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", sans-serif; margin: 2rem; color: #1f2430; background: #fafafa; }
+h1 { font-size: 1.4rem; } h2 { font-size: 1.1rem; margin-top: 2rem; border-bottom: 1px solid #ddd; }
+table { border-collapse: collapse; width: 100%; font-size: 0.85rem; background: #fff; }
+th, td { border: 1px solid #e3e3e3; padding: 4px 6px; text-align: left; vertical-align: top; }
+th { background: #f0f2f5; }
+td.lane { max-width: 14rem; overflow-wrap: anywhere; }
+.error { color: #c0392b; font-weight: 600; } .ok { color: #27ae60; } .muted { color: #888; }
+.tool_call { background: #eef6ff; } .tool_result { background: #effaf1; } .error_row { background: #fdecea; }
+.summary { display: flex; gap: 2rem; flex-wrap: wrap; }
+.summary div { background: #fff; border: 1px solid #e3e3e3; padding: 0.5rem 1rem; }
+"#;
+
+/// Render the report as one HTML document with inline CSS and no external assets.
+pub fn render_html(report: &SessionReport) -> String {
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Session {id}</title><style>{STYLE}</style></head><body>\n\
+         <h1>Session {id}</h1>\n<p class=\"muted\">{start} &ndash; {end} &middot; generated {now}</p>\n",
+        id = escape(&report.session_id),
+        start = escape(report.started_at.as_deref().unwrap_or("?")),
+        end = escape(report.ended_at.as_deref().unwrap_or("?")),
+        now = chrono::Utc::now().to_rfc3339(),
+    );
+
+    render_summary(&mut out, report);
+    render_todos(&mut out, report);
+    render_tool_calls(&mut out, report);
+    render_files(&mut out, report);
+    render_timeline(&mut out, report);
+
+    out.push_str("</body></html>\n");
+    out
+}
+
+fn render_summary(out: &mut String, report: &SessionReport) {
+    let usage = &report.usage;
+    let errors = report
+        .tool_calls
+        .iter()
+        .filter(|c| c.error == Some(true))
+        .count();
+    let _ = write!(
+        out,
+        "<h2>Summary</h2>\n<div class=\"summary\">\
+         <div>Tool calls<br><b>{}</b></div><div>Errors<br><b>{}</b></div><div>Files<br><b>{}</b></div>\
+         <div>Tokens in / out<br><b>{} / {}</b></div><div>Cache write / read<br><b>{} / {}</b></div>\
+         <div>Est. cost<br><b>${:.4}</b></div><div>Models<br><b>{}</b></div></div>\n",
+        report.tool_calls.len(),
+        errors,
+        report.files.len(),
+        usage.input,
+        usage.output,
+        usage.cache_creation,
+        usage.cache_read,
+        usage.cost_usd,
+        escape(&usage.models.join(", ")),
+    );
+}
+
+fn render_todos(out: &mut String, report: &SessionReport) {
+    out.push_str("<h2>Final todo list</h2>\n");
+    if report.todos.is_empty() {
+        out.push_str("<p class=\"muted\">No TodoWrite calls.</p>\n");
+        return;
+    }
+    out.push_str("<ul>\n");
+    for todo in &report.todos {
+        let (mark, status) = match todo.status {
+            TodoStatus::Completed => ("&#9745;", "completed"),
+            TodoStatus::InProgress => ("&#9654;", "in progress"),
+            TodoStatus::Pending => ("&#9744;", "pending"),
+        };
+        let _ = writeln!(
+            out,
+            "<li>{mark} {} <span class=\"muted\">({status})</span></li>",
+            escape(&todo.content),
+        );
+    }
+    out.push_str("</ul>\n");
+}
+
+fn render_tool_calls(out: &mut String, report: &SessionReport) {
+    out.push_str(
+        "<h2>Tool calls</h2>\n<table><tr><th>Started</th><th>Tool</th><th>Input</th><th>Duration</th><th>Status</th><th>Result</th></tr>\n",
+    );
+    for call in &report.tool_calls {
+        let duration = call
+            .duration_ms
+            .map(|ms| format!("{:.1}s", ms as f64 / 1000.0))
+            .unwrap_or_else(|| "&ndash;".to_string());
+        let status = match call.error {
+            Some(true) => "<span class=\"error\">error</span>",
+            Some(false) => "<span class=\"ok\">ok</span>",
+            None => "<span class=\"muted\">no result</span>",
+        };
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{duration}</td><td>{status}</td><td>{}</td></tr>",
+            escape(&call.started_at),
+            escape(&call.tool),
+            escape(&call.input),
+            escape(&call.result),
+        );
+    }
+    out.push_str("</table>\n");
+}
+
+fn render_files(out: &mut String, report: &SessionReport) {
+    out.push_str("<h2>Files touched</h2>\n<table><tr><th>Path</th><th>Reads</th><th>Edits</th><th>Writes</th></tr>\n");
+    for (path, touch) in &report.files {
+        let _ = writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape(path),
+            touch.reads,
+            touch.edits,
+            touch.writes
+        );
+    }
+    out.push_str("</table>\n");
+}
+
+fn render_timeline(out: &mut String, report: &SessionReport) {
+    out.push_str("<h2>Timeline by agent role</h2>\n<table><tr><th>Time</th>");
+    for lane in LANES {
+        let _ = write!(out, "<th>{}</th>", agent_id_for_type(lane));
+    }
+    out.push_str("</tr>\n");
+
+    for item in &report.timeline {
+        let class = match item.entry_type {
+            LogEntryType::ToolCall => "tool_call",
+            LogEntryType::ToolResult => "tool_result",
+            LogEntryType::Error => "error_row",
+            _ => "",
+        };
+        let _ = write!(
+            out,
+            "<tr class=\"{class}\"><td>{}</td>",
+            escape(&item.timestamp)
+        );
+        for lane in LANES {
+            if lane == item.agent_type {
+                let _ = write!(out, "<td class=\"lane\">{}</td>", escape(&item.text));
+            } else {
+                out.push_str("<td></td>");
+            }
+        }
+        out.push_str("</tr>\n");
+    }
+    out.push_str("</table>\n");
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
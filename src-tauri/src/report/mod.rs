@@ -0,0 +1,298 @@
+mod html;
+
+use crate::models::{AgentType, FileAccess, LogEntryType, TodoItem, ToolInput};
+use crate::redact::Redactor;
+use crate::watcher::{determine_agent_type, parse_session_line};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub use html::render_html;
+
+/// One parsed entry placed in its agent's lane
+#[derive(Debug, Clone)]
+pub struct TimelineItem {
+    pub timestamp: String,
+    pub agent_type: AgentType,
+    pub entry_type: LogEntryType,
+    pub text: String,
+}
+
+/// A tool_use block and, when found, its matching tool_result
+#[derive(Debug, Clone)]
+pub struct ToolCallRow {
+    pub tool: String,
+    pub input: String,
+    pub started_at: String,
+    pub duration_ms: Option<i64>,
+    /// `None` while no result was seen
+    pub error: Option<bool>,
+    pub result: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileTouch {
+    pub reads: usize,
+    pub edits: usize,
+    pub writes: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TokenUsage {
+    pub input: u64,
+    pub output: u64,
+    pub cache_creation: u64,
+    pub cache_read: u64,
+    /// Estimated from list prices; unknown models are not priced
+    pub cost_usd: f64,
+    pub models: Vec<String>,
+}
+
+/// Everything the HTML report shows, derived from a session transcript
+#[derive(Debug, Clone, Default)]
+pub struct SessionReport {
+    pub session_id: String,
+    pub started_at: Option<String>,
+    pub ended_at: Option<String>,
+    pub timeline: Vec<TimelineItem>,
+    pub tool_calls: Vec<ToolCallRow>,
+    pub files: BTreeMap<String, FileTouch>,
+    pub usage: TokenUsage,
+    pub todos: Vec<TodoItem>,
+}
+
+impl SessionReport {
+    /// Build a report from raw transcript lines.
+    ///
+    /// The timeline uses the same parser as the live view; tool calls, files, usage
+    /// and todos walk every content block since one line may hold several.
+    /// Secrets are masked with `redactor` before anything is recorded.
+    pub fn from_lines(session_id: &str, lines: &[String], redactor: &Redactor) -> Self {
+        let mut report = SessionReport {
+            session_id: session_id.to_string(),
+            ..Default::default()
+        };
+        let mut pending: HashMap<String, usize> = HashMap::new();
+        let mut counted_messages: HashSet<String> = HashSet::new();
+
+        for line in lines {
+            if let Some(mut entry) = parse_session_line(line) {
+                redactor.redact_entry(&mut entry);
+                let timestamp = entry.timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+                if entry.timestamp_source.is_logged() {
+                    report.started_at.get_or_insert_with(|| timestamp.clone());
+                    report.ended_at = Some(timestamp.clone());
+                }
+                report.timeline.push(TimelineItem {
+                    agent_type: determine_agent_type(&entry),
+                    timestamp,
+                    entry_type: entry.entry_type,
+                    text: entry.content.chars().take(200).collect(),
+                });
+            }
+
+            let Ok(mut json) = serde_json::from_str::<Value>(line) else {
+                continue;
+            };
+            redactor.redact_value(&mut json);
+            let timestamp = json.get("timestamp").and_then(|v| v.as_str()).unwrap_or("");
+
+            report.add_usage(&json, &mut counted_messages);
+
+            let blocks = json
+                .pointer("/message/content")
+                .and_then(|v| v.as_array())
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            for block in blocks {
+                match block.get("type").and_then(|v| v.as_str()) {
+                    Some("tool_use") => report.add_tool_use(block, timestamp, &mut pending),
+                    Some("tool_result") => report.add_tool_result(block, timestamp, &mut pending),
+                    _ => {}
+                }
+            }
+        }
+
+        report
+    }
+
+    fn add_tool_use(
+        &mut self,
+        block: &Value,
+        timestamp: &str,
+        pending: &mut HashMap<String, usize>,
+    ) {
+        let tool = block
+            .get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+        let input = block.get("input").cloned().unwrap_or(Value::Null);
+
+        let typed = ToolInput::parse(&tool, &input);
+        if let Some((path, access)) = typed.file_access() {
+            let touch = self.files.entry(path.to_string()).or_default();
+            match access {
+                FileAccess::Read => touch.reads += 1,
+                FileAccess::Edit => touch.edits += 1,
+                FileAccess::Write => touch.writes += 1,
+            }
+        }
+        if let ToolInput::TodoWrite(plan) = typed {
+            self.todos = plan.todos;
+        }
+
+        if let Some(id) = block.get("id").and_then(|v| v.as_str()) {
+            pending.insert(id.to_string(), self.tool_calls.len());
+        }
+        self.tool_calls.push(ToolCallRow {
+            input: crate::watcher::summarize_tool_input(&input),
+            tool,
+            started_at: timestamp.to_string(),
+            duration_ms: None,
+            error: None,
+            result: String::new(),
+        });
+    }
+
+    fn add_tool_result(
+        &mut self,
+        block: &Value,
+        timestamp: &str,
+        pending: &mut HashMap<String, usize>,
+    ) {
+        let Some(index) = block
+            .get("tool_use_id")
+            .and_then(|v| v.as_str())
+            .and_then(|id| pending.remove(id))
+        else {
+            return;
+        };
+
+        let row = &mut self.tool_calls[index];
+        row.error = Some(
+            block
+                .get("is_error")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+        );
+        row.duration_ms = match (parse_time(&row.started_at), parse_time(timestamp)) {
+            (Some(start), Some(end)) => Some((end - start).num_milliseconds().max(0)),
+            _ => None,
+        };
+        row.result = match block.get("content") {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Array(parts)) => parts
+                .iter()
+                .filter_map(|p| p.get("text").and_then(|v| v.as_str()))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            _ => String::new(),
+        }
+        .chars()
+        .take(200)
+        .collect();
+    }
+
+    /// Sum `message.usage`, counting each assistant message once
+    /// (transcripts repeat it on every line split from the same response).
+    fn add_usage(&mut self, json: &Value, counted: &mut HashSet<String>) {
+        let Some(usage) = json.pointer("/message/usage") else {
+            return;
+        };
+        if let Some(id) = json.pointer("/message/id").and_then(|v| v.as_str()) {
+            if !counted.insert(id.to_string()) {
+                return;
+            }
+        }
+
+        let field = |name: &str| usage.get(name).and_then(|v| v.as_u64()).unwrap_or(0);
+        let (input, output) = (field("input_tokens"), field("output_tokens"));
+        let (cache_creation, cache_read) = (
+            field("cache_creation_input_tokens"),
+            field("cache_read_input_tokens"),
+        );
+        self.usage.input += input;
+        self.usage.output += output;
+        self.usage.cache_creation += cache_creation;
+        self.usage.cache_read += cache_read;
+
+        let model = json
+            .pointer("/message/model")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+        if let Some((input_price, output_price)) = model_prices(model) {
+            self.usage.cost_usd += (input as f64 * input_price
+                + cache_creation as f64 * input_price * 1.25
+                + cache_read as f64 * input_price * 0.1
+                + output as f64 * output_price)
+                / 1_000_000.0;
+        }
+        if !model.is_empty() && !self.usage.models.iter().any(|m| m == model) {
+            self.usage.models.push(model.to_string());
+        }
+    }
+}
+
+/// USD per million input/output tokens by model family
+fn model_prices(model: &str) -> Option<(f64, f64)> {
+    let model = model.to_ascii_lowercase();
+    if model.contains("opus") {
+        Some((15.0, 75.0))
+    } else if model.contains("sonnet") {
+        Some((3.0, 15.0))
+    } else if model.contains("haiku") {
+        Some((0.8, 4.0))
+    } else {
+        None
+    }
+}
+
+fn parse_time(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TodoStatus;
+
+    fn lines() -> Vec<String> {
+        [
+            r#"{"type":"assistant","timestamp":"2025-01-01T00:00:00Z","message":{"id":"m1","model":"claude-sonnet-4","usage":{"input_tokens":1000,"output_tokens":100},"content":[{"type":"tool_use","id":"t1","name":"Edit","input":{"file_path":"/src/a.rs"}},{"type":"tool_use","id":"t2","name":"TodoWrite","input":{"todos":[{"content":"Fix bug","status":"completed","activeForm":"Fixing bug"}]}}]}}"#,
+            r#"{"type":"assistant","timestamp":"2025-01-01T00:00:00Z","message":{"id":"m1","model":"claude-sonnet-4","usage":{"input_tokens":1000,"output_tokens":100},"content":[{"type":"text","text":"done"}]}}"#,
+            r#"{"type":"user","timestamp":"2025-01-01T00:00:02.500Z","message":{"content":[{"type":"tool_result","tool_use_id":"t1","content":"failed","is_error":true}]}}"#,
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn test_report_collects_tools_files_usage_and_todos() {
+        let report = SessionReport::from_lines("s1", &lines(), &Redactor::disabled());
+
+        assert_eq!(report.timeline.len(), 3);
+        assert_eq!(report.tool_calls.len(), 2);
+        assert_eq!(report.tool_calls[0].duration_ms, Some(2500));
+        assert_eq!(report.tool_calls[0].error, Some(true));
+        assert_eq!(report.tool_calls[1].error, None);
+        assert_eq!(report.files["/src/a.rs"].edits, 1);
+        assert_eq!(report.usage.input, 1000);
+        assert!((report.usage.cost_usd - 0.0045).abs() < 1e-9);
+        assert_eq!(report.todos[0].status, TodoStatus::Completed);
+    }
+
+    #[test]
+    fn test_render_escapes_content() {
+        let mut report = SessionReport::from_lines("s1", &lines(), &Redactor::disabled());
+        report.tool_calls[0].input = "<script>alert(1)</script>".to_string();
+
+        let html = render_html(&report);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>alert"));
+    }
+}
@@ -0,0 +1,287 @@
+use crate::history::{self, TimeRange};
+use crate::models::{AgentType, LogEntry, LogEntryType};
+use crate::redact::Redactor;
+use crate::watcher::determine_agent_type;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 500;
+
+/// Characters of context kept on each side of the first match
+const SNIPPET_CONTEXT: usize = 60;
+
+/// Full-text query over persisted transcripts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchQuery {
+    /// Case-insensitive text matched against content (message text, bash command,
+    /// file path...) and tool name; empty matches everything
+    pub text: String,
+    pub session_id: Option<String>,
+    /// Substring of the `~/.claude/projects` folder name
+    pub project: Option<String>,
+    /// Any of these roles; all when empty
+    pub agent_types: Vec<AgentType>,
+    /// Any of these entry types; all when empty
+    pub entry_types: Vec<LogEntryType>,
+    /// RFC 3339 or `YYYY-MM-DD`, inclusive
+    pub from: Option<String>,
+    /// RFC 3339 or `YYYY-MM-DD`, exclusive
+    pub to: Option<String>,
+    pub offset: usize,
+    /// Page size (default 50, max 500)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub entry: LogEntry,
+    pub project: String,
+    pub agent_type: AgentType,
+    /// Excerpt around the first match
+    pub snippet: String,
+}
+
+/// One page of hits, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchPage {
+    pub hits: Vec<SearchHit>,
+    /// Matches across all pages
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+/// Search every transcript under `~/.claude/projects`.
+pub fn search(query: &SearchQuery, redactor: &Redactor) -> Result<SearchPage, String> {
+    let files = match &query.session_id {
+        Some(session_id) => vec![history::find_session_file(session_id)?],
+        None => history::projects_dir()
+            .map(|root| history::list_session_files(&root))
+            .unwrap_or_default(),
+    };
+    search_files(query, files, redactor)
+}
+
+fn search_files(
+    query: &SearchQuery,
+    files: Vec<PathBuf>,
+    redactor: &Redactor,
+) -> Result<SearchPage, String> {
+    let range = TimeRange::parse(query.from.as_deref(), query.to.as_deref())?;
+    let needle = fold_case(query.text.trim());
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let mut hits = Vec::new();
+    for file in files {
+        let project = history::project_name(&file);
+        if query
+            .project
+            .as_ref()
+            .is_some_and(|p| !project.to_lowercase().contains(&p.to_lowercase()))
+        {
+            continue;
+        }
+        // A transcript untouched since `from` cannot contain newer entries.
+        if let (Some(from), Ok(modified)) = (range.from, file.metadata().and_then(|m| m.modified()))
+        {
+            if chrono::DateTime::<chrono::Utc>::from(modified) < from {
+                continue;
+            }
+        }
+
+        let entries = match history::read_entries(&file, redactor) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Skipping unreadable transcript in search: {}", e);
+                continue;
+            }
+        };
+        for entry in entries {
+            if let Some(hit) = match_entry(query, &range, &needle, entry, &project) {
+                hits.push(hit);
+            }
+        }
+    }
+
+    hits.sort_by_key(|h| std::cmp::Reverse(history::entry_time(&h.entry)));
+    let total = hits.len();
+    let hits = hits.into_iter().skip(query.offset).take(limit).collect();
+
+    Ok(SearchPage {
+        hits,
+        total,
+        offset: query.offset,
+        limit,
+    })
+}
+
+fn match_entry(
+    query: &SearchQuery,
+    range: &TimeRange,
+    needle: &str,
+    entry: LogEntry,
+    project: &str,
+) -> Option<SearchHit> {
+    if !query.entry_types.is_empty() && !query.entry_types.contains(&entry.entry_type) {
+        return None;
+    }
+    let agent_type = determine_agent_type(&entry);
+    if !query.agent_types.is_empty() && !query.agent_types.contains(&agent_type) {
+        return None;
+    }
+    if !range.contains(&entry) {
+        return None;
+    }
+
+    let snippet = if needle.is_empty() {
+        snippet_at(&entry.content, 0, 0)
+    } else if let Some(at) = find_ignore_case(&entry.content, needle) {
+        snippet_at(&entry.content, at, needle.chars().count())
+    } else if entry
+        .tool_name
+        .as_ref()
+        .is_some_and(|t| fold_case(t).contains(needle))
+    {
+        snippet_at(&entry.content, 0, 0)
+    } else {
+        return None;
+    };
+
+    Some(SearchHit {
+        entry,
+        project: project.to_string(),
+        agent_type,
+        snippet,
+    })
+}
+
+/// Lowercase char by char, one char out per char in, so char offsets carry over.
+fn fold_case(text: &str) -> String {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// Char index of the first case-insensitive occurrence of `needle` (already `fold_case`d).
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    let hay: Vec<char> = fold_case(haystack).chars().collect();
+    let needle: Vec<char> = needle.chars().collect();
+    if needle.len() > hay.len() {
+        return None;
+    }
+    (0..=hay.len() - needle.len()).find(|&i| hay[i..i + needle.len()] == needle[..])
+}
+
+/// Excerpt of `text` around chars `[at, at + len)`, single-lined and ellipsized.
+fn snippet_at(text: &str, at: usize, len: usize) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let start = at.saturating_sub(SNIPPET_CONTEXT);
+    let end = (at + len + SNIPPET_CONTEXT).min(chars.len());
+
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push('…');
+    }
+    snippet.extend(
+        chars[start..end]
+            .iter()
+            .map(|&c| if c.is_whitespace() { ' ' } else { c }),
+    );
+    if end < chars.len() {
+        snippet.push('…');
+    }
+    snippet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_transcript(
+        root: &std::path::Path,
+        project: &str,
+        session: &str,
+        lines: &[&str],
+    ) -> PathBuf {
+        let dir = root.join(project);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{session}.jsonl"));
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_search_filters_and_paginates() {
+        let root =
+            std::env::temp_dir().join(format!("agents-office-search-{}", std::process::id()));
+        let files = vec![
+            write_transcript(
+                &root,
+                "-Users-me-app",
+                "s1",
+                &[
+                    r#"{"timestamp":"2025-01-01T00:00:01Z","message":{"content":[{"type":"tool_use","id":"t1","name":"Bash","input":{"command":"cargo test --workspace"}}]}}"#,
+                    r#"{"timestamp":"2025-01-01T00:00:02Z","message":{"content":[{"type":"text","text":"Running CARGO now"}]}}"#,
+                ],
+            ),
+            write_transcript(
+                &root,
+                "-Users-me-other",
+                "s2",
+                &[
+                    r#"{"timestamp":"2025-01-02T00:00:00Z","message":{"content":[{"type":"tool_use","id":"t2","name":"Read","input":{"file_path":"/src/cargo.rs"}}]}}"#,
+                ],
+            ),
+        ];
+
+        let query =
+            |q: SearchQuery| search_files(&q, files.clone(), &Redactor::disabled()).unwrap();
+
+        let all = query(SearchQuery {
+            text: "cargo".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(all.total, 3);
+        assert_eq!(all.hits[0].entry.session_id.as_deref(), Some("s2"));
+
+        let validators = query(SearchQuery {
+            text: "cargo".to_string(),
+            agent_types: vec![AgentType::Validator],
+            ..Default::default()
+        });
+        assert_eq!(validators.total, 1);
+        assert_eq!(validators.hits[0].snippet, "cargo test --workspace");
+
+        let page = query(SearchQuery {
+            text: "cargo".to_string(),
+            project: Some("app".to_string()),
+            entry_types: vec![LogEntryType::Message],
+            offset: 0,
+            limit: Some(1),
+            ..Default::default()
+        });
+        assert_eq!((page.total, page.hits.len()), (1, 1));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_snippet_is_ellipsized_around_match() {
+        let text = format!("{}needle{}", "a".repeat(100), "b".repeat(100));
+        let at = find_ignore_case(&text, "needle").unwrap();
+        let snippet = snippet_at(&text, at, 6);
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'));
+        assert!(snippet.contains("needle"));
+        assert_eq!(snippet.chars().count(), 2 * SNIPPET_CONTEXT + 6 + 2);
+
+        // `İ` lowercases to two chars with `str::to_lowercase`; offsets must not shift.
+        let text = "İstanbul cargo";
+        assert_eq!(find_ignore_case(text, &fold_case("İSTANBUL")), Some(0));
+        assert_eq!(find_ignore_case(text, &fold_case("Cargo")), Some(9));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::events::EventHub;
+use crate::models::SequencedEvent;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::http::{header, HeaderMap, StatusCode, Uri};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::sync::broadcast;
+
+/// Embedded HTTP + WebSocket server for viewing the office in a browser
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerSettings {
+    pub enabled: bool,
+    /// Bind address; keep the loopback default unless the office should be reachable on the LAN
+    pub host: String,
+    pub port: u16,
+    /// Extra page origins allowed to open `/ws`, e.g. `http://localhost:1420` for `npm run dev`
+    pub allowed_origins: Vec<String>,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".to_string(),
+            port: 4780,
+            allowed_origins: Vec::new(),
+        }
+    }
+}
+
+struct ServerState<R: Runtime> {
+    app: AppHandle<R>,
+    settings: ServerSettings,
+}
+
+impl<R: Runtime> Clone for ServerState<R> {
+    fn clone(&self) -> Self {
+        Self {
+            app: self.app.clone(),
+            settings: self.settings.clone(),
+        }
+    }
+}
+
+/// Serve the built frontend on `/` and stream `AppEvent` JSON on `/ws`.
+pub async fn start_server<R: Runtime>(
+    app: AppHandle<R>,
+    settings: ServerSettings,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let addr = format!("{}:{}", settings.host, settings.port);
+    let router = Router::new()
+        .route("/ws", get(ws_handler::<R>))
+        .fallback(serve_asset::<R>)
+        .with_state(ServerState {
+            app,
+            settings: settings.clone(),
+        });
+
+    let listener = tokio::net::TcpListener::bind(&addr).await?;
+    tracing::info!("Browser office available at http://{}", addr);
+    axum::serve(listener, router).await?;
+    Ok(())
+}
+
+async fn ws_handler<R: Runtime>(
+    ws: WebSocketUpgrade,
+    State(state): State<ServerState<R>>,
+    headers: HeaderMap,
+) -> Response {
+    if let Err(status) = check_origin(&headers, &state.settings) {
+        tracing::warn!(
+            "Rejected WebSocket upgrade from origin {:?}",
+            headers.get(header::ORIGIN)
+        );
+        return status.into_response();
+    }
+    let rx = state.app.state::<EventHub>().subscribe();
+    ws.on_upgrade(move |socket| stream_events(socket, rx))
+}
+
+/// Only accept upgrades addressed to this server and, from a browser, sent by its own page.
+///
+/// The `Host` check stops DNS rebinding; the `Origin` check stops other web pages.
+/// Clients that send no `Origin` (not a browser) only need a valid `Host`.
+fn check_origin(headers: &HeaderMap, settings: &ServerSettings) -> Result<(), StatusCode> {
+    let host = headers
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .filter(|host| host_allowed(host, &settings.host, settings.port))
+        .ok_or(StatusCode::FORBIDDEN)?;
+    match headers.get(header::ORIGIN) {
+        None => Ok(()),
+        Some(origin) => {
+            let origin = origin.to_str().map_err(|_| StatusCode::FORBIDDEN)?;
+            if origin == format!("http://{host}")
+                || settings.allowed_origins.iter().any(|o| o == origin)
+            {
+                Ok(())
+            } else {
+                Err(StatusCode::FORBIDDEN)
+            }
+        }
+    }
+}
+
+/// Whether `host` (`name:port`) names a server bound to `bind:port` rather than a
+/// domain resolving to it (DNS rebinding).
+pub(crate) fn host_allowed(host: &str, bind: &str, port: u16) -> bool {
+    let Some((name, host_port)) = host.rsplit_once(':') else {
+        return false;
+    };
+    if host_port.parse() != Ok(port) {
+        return false;
+    }
+    let name = name.trim_start_matches('[').trim_end_matches(']');
+    let is_local = |name: &str| {
+        name.eq_ignore_ascii_case("localhost")
+            || name.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+    };
+    match bind.parse::<IpAddr>() {
+        // Bound to every interface: any address of this machine, never a domain name.
+        Ok(bind) if bind.is_unspecified() => is_local(name) || name.parse::<IpAddr>().is_ok(),
+        Ok(bind) if bind.is_loopback() => is_local(name),
+        _ => name.eq_ignore_ascii_case(bind),
+    }
+}
+
+async fn stream_events(mut socket: WebSocket, mut rx: broadcast::Receiver<SequencedEvent>) {
+    loop {
+        tokio::select! {
+            event = rx.recv() => match event {
+                Ok(event) => {
+                    let Ok(json) = serde_json::to_string(&event) else {
+                        continue;
+                    };
+                    if socket.send(Message::Text(json.into())).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("WebSocket client lagging; skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Clients are receive-only; ignore anything they send.
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+async fn serve_asset<R: Runtime>(State(state): State<ServerState<R>>, uri: Uri) -> Response {
+    let path = match uri.path() {
+        "/" => "/index.html",
+        path => path,
+    };
+    if path.contains("..") {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+
+    // Unknown paths fall back to index.html so client-side routes still load.
+    let resolver = state.app.asset_resolver();
+    let asset = resolver
+        .get(path.to_string())
+        .or_else(|| resolver.get("/index.html".to_string()));
+
+    match asset {
+        Some(asset) => ([(header::CONTENT_TYPE, asset.mime_type)], asset.bytes).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(host: &str, origin: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, host.parse().unwrap());
+        if let Some(origin) = origin {
+            headers.insert(header::ORIGIN, origin.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_rejects_foreign_origin() {
+        let settings = ServerSettings::default();
+        assert_eq!(
+            check_origin(
+                &headers("127.0.0.1:4780", Some("http://evil.example")),
+                &settings
+            ),
+            Err(StatusCode::FORBIDDEN)
+        );
+        // DNS rebinding: the page's own origin, but not our host name.
+        assert_eq!(
+            check_origin(
+                &headers("evil.example:4780", Some("http://evil.example:4780")),
+                &settings
+            ),
+            Err(StatusCode::FORBIDDEN)
+        );
+    }
+
+    #[test]
+    fn test_accepts_own_origin() {
+        let settings = ServerSettings::default();
+        for host in ["127.0.0.1:4780", "localhost:4780"] {
+            let origin = format!("http://{host}");
+            assert_eq!(
+                check_origin(&headers(host, Some(&origin)), &settings),
+                Ok(())
+            );
+        }
+        assert_eq!(
+            check_origin(&headers("127.0.0.1:4780", None), &settings),
+            Ok(())
+        );
+        assert_eq!(
+            check_origin(&headers("127.0.0.1:9999", None), &settings),
+            Err(StatusCode::FORBIDDEN)
+        );
+
+        let settings = ServerSettings {
+            allowed_origins: vec!["http://localhost:1420".to_string()],
+            ..ServerSettings::default()
+        };
+        assert_eq!(
+            check_origin(
+                &headers("127.0.0.1:4780", Some("http://localhost:1420")),
+                &settings
+            ),
+            Ok(())
+        );
+    }
+}
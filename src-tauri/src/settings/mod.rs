@@ -0,0 +1,77 @@
+use crate::filters::FilterSettings;
+use crate::ingest::IngestSettings;
+use crate::metrics::MetricsSettings;
+use crate::notifications::NotificationSettings;
+use crate::office::OfficeSettings;
+use crate::projects::ProjectSettings;
+use crate::redact::RedactionSettings;
+use crate::server::ServerSettings;
+use crate::telemetry::TelemetrySettings;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// User-configurable backend settings, persisted as JSON in the app config directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub notifications: NotificationSettings,
+    pub server: ServerSettings,
+    pub ingest: IngestSettings,
+    pub metrics: MetricsSettings,
+    pub telemetry: TelemetrySettings,
+    pub redaction: RedactionSettings,
+    pub projects: ProjectSettings,
+    pub filters: FilterSettings,
+    pub office: OfficeSettings,
+}
+
+/// Holds the current settings and writes them back to disk on save.
+pub struct SettingsStore {
+    path: Option<PathBuf>,
+    current: RwLock<AppSettings>,
+}
+
+impl SettingsStore {
+    /// Load settings from `<config_dir>/agents-office/settings.json`, falling back to defaults.
+    pub fn load() -> Self {
+        let path = dirs::config_dir().map(|d| d.join("agents-office").join("settings.json"));
+
+        let current = path
+            .as_ref()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .and_then(|raw| match serde_json::from_str::<AppSettings>(&raw) {
+                Ok(settings) => Some(settings),
+                Err(e) => {
+                    tracing::warn!("Ignoring invalid settings file {:?}: {}", path, e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+
+        Self {
+            path,
+            current: RwLock::new(current),
+        }
+    }
+
+    pub fn get(&self) -> AppSettings {
+        self.current
+            .read()
+            .expect("SettingsStore lock poisoned")
+            .clone()
+    }
+
+    pub fn save(&self, settings: AppSettings) -> Result<(), String> {
+        if let Some(path) = &self.path {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            let raw = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+            std::fs::write(path, raw).map_err(|e| e.to_string())?;
+        }
+
+        *self.current.write().expect("SettingsStore lock poisoned") = settings;
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+mod otlp;
+mod spans;
+
+use crate::models::LogEntry;
+use otlp::OtlpExporter;
+use serde::{Deserialize, Serialize};
+use spans::SessionTracer;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// OpenTelemetry trace export over OTLP/HTTP (JSON)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetrySettings {
+    pub enabled: bool,
+    /// Full traces URL of the collector, e.g. `http://localhost:4318/v1/traces`
+    pub endpoint: String,
+    pub service_name: String,
+    /// Sessions without new entries for this long are closed and exported
+    pub session_idle_secs: u64,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: "http://localhost:4318/v1/traces".to_string(),
+            service_name: "agents-office".to_string(),
+            session_idle_secs: 300,
+        }
+    }
+}
+
+/// Turns the entry stream into spans and ships finished ones to the collector
+pub struct TraceExporter {
+    tracer: Mutex<SessionTracer>,
+    exporter: OtlpExporter,
+    session_idle: Duration,
+}
+
+impl TraceExporter {
+    pub fn new(settings: &TelemetrySettings) -> Result<Self, String> {
+        Ok(Self {
+            tracer: Mutex::new(SessionTracer::new()),
+            exporter: OtlpExporter::new(settings.endpoint.clone(), settings.service_name.clone())?,
+            session_idle: Duration::from_secs(settings.session_idle_secs),
+        })
+    }
+
+    pub fn observe(&self, entries: &[LogEntry]) {
+        let now = Instant::now();
+        let mut tracer = self.tracer.lock().expect("SessionTracer mutex poisoned");
+        for entry in entries {
+            tracer.observe(entry, now);
+        }
+    }
+
+    /// Close idle sessions and export every span finished since the last flush.
+    /// Called periodically from a background thread (the HTTP client blocks).
+    pub fn flush(&self) {
+        let spans = {
+            let mut tracer = self.tracer.lock().expect("SessionTracer mutex poisoned");
+            tracer.close_idle(Instant::now(), self.session_idle);
+            tracer.take_finished()
+        };
+
+        if let Err(e) = self.exporter.export(&spans) {
+            tracing::warn!("Dropped {} spans, OTLP export failed: {}", spans.len(), e);
+        }
+    }
+}
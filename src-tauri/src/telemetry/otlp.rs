@@ -0,0 +1,163 @@
+use crate::telemetry::spans::SpanRecord;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// OTLP span kind INTERNAL
+const SPAN_KIND_INTERNAL: u8 = 1;
+/// OTLP status codes
+const STATUS_OK: u8 = 1;
+const STATUS_ERROR: u8 = 2;
+
+/// Sends spans to an OTLP/HTTP collector using the JSON encoding
+pub struct OtlpExporter {
+    client: reqwest::blocking::Client,
+    endpoint: String,
+    service_name: String,
+}
+
+impl OtlpExporter {
+    pub fn new(endpoint: String, service_name: String) -> Result<Self, String> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .map_err(|e| e.to_string())?;
+        Ok(Self {
+            client,
+            endpoint,
+            service_name,
+        })
+    }
+
+    pub fn export(&self, spans: &[SpanRecord]) -> Result<(), String> {
+        if spans.is_empty() {
+            return Ok(());
+        }
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .json(&encode_spans(&self.service_name, spans))
+            .send()
+            .map_err(|e| e.to_string())?;
+
+        if !response.status().is_success() {
+            return Err(format!("collector responded with {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Build an `ExportTraceServiceRequest` in OTLP/JSON form.
+pub fn encode_spans(service_name: &str, spans: &[SpanRecord]) -> Value {
+    let spans: Vec<Value> = spans
+        .iter()
+        .map(|span| {
+            let mut encoded = json!({
+                "traceId": span.trace_id,
+                "spanId": span.span_id,
+                "name": span.name,
+                "kind": SPAN_KIND_INTERNAL,
+                "startTimeUnixNano": span.start_unix_nanos.to_string(),
+                "endTimeUnixNano": span.end_unix_nanos.to_string(),
+                "attributes": span
+                    .attributes
+                    .iter()
+                    .map(|(k, v)| string_attribute(k, v))
+                    .collect::<Vec<_>>(),
+                "status": match &span.error {
+                    Some(message) => json!({ "code": STATUS_ERROR, "message": message }),
+                    None => json!({ "code": STATUS_OK }),
+                },
+            });
+            if let Some(parent) = &span.parent_span_id {
+                encoded["parentSpanId"] = json!(parent);
+            }
+            encoded
+        })
+        .collect();
+
+    json!({
+        "resourceSpans": [{
+            "resource": { "attributes": [string_attribute("service.name", service_name)] },
+            "scopeSpans": [{
+                "scope": { "name": "agents-office", "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    fn span(error: Option<&str>) -> SpanRecord {
+        SpanRecord {
+            trace_id: "0f8fad5bd9cb469fa16570867728950e".to_string(),
+            span_id: "00000000000000aa".to_string(),
+            parent_span_id: Some("00000000000000bb".to_string()),
+            name: "Bash".to_string(),
+            start_unix_nanos: 1,
+            end_unix_nanos: 2,
+            attributes: vec![("tool.name".to_string(), "Bash".to_string())],
+            error: error.map(String::from),
+        }
+    }
+
+    /// Minimal collector stand-in: accepts one request and returns its body.
+    fn spawn_collector() -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/v1/traces", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(v) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = v.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}")
+                .unwrap();
+            String::from_utf8(body).unwrap()
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_encode_sets_parent_and_error_status() {
+        let encoded = encode_spans("agents-office", &[span(Some("exit 1"))]);
+        let encoded_span = &encoded["resourceSpans"][0]["scopeSpans"][0]["spans"][0];
+        assert_eq!(encoded_span["parentSpanId"], "00000000000000bb");
+        assert_eq!(encoded_span["status"]["code"], STATUS_ERROR);
+        assert_eq!(encoded_span["startTimeUnixNano"], "1");
+    }
+
+    #[test]
+    fn test_export_posts_to_collector() {
+        let (url, collector) = spawn_collector();
+        let exporter = OtlpExporter::new(url, "agents-office".to_string()).unwrap();
+
+        exporter.export(&[span(None)]).unwrap();
+
+        let body: Value = serde_json::from_str(&collector.join().unwrap()).unwrap();
+        let spans = &body["resourceSpans"][0]["scopeSpans"][0]["spans"];
+        assert_eq!(spans[0]["name"], "Bash");
+        assert_eq!(spans[0]["traceId"], "0f8fad5bd9cb469fa16570867728950e");
+    }
+}
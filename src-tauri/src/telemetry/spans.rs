@@ -0,0 +1,331 @@
+use crate::models::{LogEntry, LogEntryType};
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::time::{Duration, Instant};
+
+/// A finished span ready for export
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanRecord {
+    /// 32 lowercase hex chars
+    pub trace_id: String,
+    /// 16 lowercase hex chars
+    pub span_id: String,
+    pub parent_span_id: Option<String>,
+    pub name: String,
+    pub start_unix_nanos: u64,
+    pub end_unix_nanos: u64,
+    pub attributes: Vec<(String, String)>,
+    /// Error status message; `None` means OK
+    pub error: Option<String>,
+}
+
+struct OpenSpan {
+    span_id: String,
+    /// Set when the span opens, so a tool stays under the turn that issued it
+    parent: Option<String>,
+    name: String,
+    start_unix_nanos: u64,
+    attributes: Vec<(String, String)>,
+}
+
+struct SessionTrace {
+    trace_id: String,
+    root: OpenSpan,
+    turn: Option<OpenSpan>,
+    turn_has_tools: bool,
+    turn_count: usize,
+    /// Open tool spans keyed by tool_use id (or tool name as a fallback)
+    tools: HashMap<String, OpenSpan>,
+    last_seen_unix_nanos: u64,
+    last_activity: Instant,
+}
+
+/// Builds session → turn → tool span trees from the parsed entry stream.
+///
+/// A turn opens on an assistant/user message and closes when a message follows
+/// its tool calls, approximating one request/response round with Claude.
+pub struct SessionTracer {
+    sessions: HashMap<String, SessionTrace>,
+    finished: Vec<SpanRecord>,
+    id_seed: u64,
+    next_span: u64,
+}
+
+impl SessionTracer {
+    pub fn new() -> Self {
+        Self {
+            sessions: HashMap::new(),
+            finished: Vec::new(),
+            id_seed: RandomState::new().hash_one("agents-office"),
+            next_span: 0,
+        }
+    }
+
+    pub fn observe(&mut self, entry: &LogEntry, now: Instant) {
+        let at = entry_unix_nanos(entry);
+        let session_id = entry.session_id.as_deref().unwrap_or("unknown");
+
+        if !self.sessions.contains_key(session_id) {
+            let trace = SessionTrace {
+                trace_id: trace_id_for_session(session_id, self.id_seed),
+                root: self.open_span(
+                    "claude.session",
+                    None,
+                    at,
+                    vec![("session.id".to_string(), session_id.to_string())],
+                ),
+                turn: None,
+                turn_has_tools: false,
+                turn_count: 0,
+                tools: HashMap::new(),
+                last_seen_unix_nanos: at,
+                last_activity: now,
+            };
+            self.sessions.insert(session_id.to_string(), trace);
+        }
+
+        let mut trace = self
+            .sessions
+            .remove(session_id)
+            .expect("session trace inserted above");
+        trace.last_seen_unix_nanos = trace.last_seen_unix_nanos.max(at);
+        trace.last_activity = now;
+
+        match entry.entry_type {
+            LogEntryType::Message if trace.turn.is_none() || trace.turn_has_tools => {
+                self.start_turn(&mut trace, at);
+            }
+            LogEntryType::ToolCall => {
+                if trace.turn.is_none() {
+                    self.start_turn(&mut trace, at);
+                }
+                let name = entry
+                    .tool_name
+                    .clone()
+                    .unwrap_or_else(|| "tool".to_string());
+                let mut attributes = vec![
+                    ("tool.name".to_string(), name.clone()),
+                    (
+                        "tool.input".to_string(),
+                        entry.content.chars().take(200).collect(),
+                    ),
+                ];
+                if let Some(id) = &entry.tool_use_id {
+                    attributes.push(("tool.use_id".to_string(), id.clone()));
+                }
+                let turn_id = trace.turn.as_ref().map(|t| t.span_id.clone());
+                let span = self.open_span(&name, turn_id, at, attributes);
+                trace.tools.insert(tool_key(entry), span);
+                trace.turn_has_tools = true;
+            }
+            LogEntryType::ToolResult | LogEntryType::Error => {
+                if let Some(span) = trace.tools.remove(&tool_key(entry)) {
+                    let error = (entry.entry_type == LogEntryType::Error)
+                        .then(|| entry.content.chars().take(200).collect());
+                    self.finish(&trace.trace_id, span, at, error);
+                }
+            }
+            LogEntryType::SessionEnd => {
+                self.close_session(trace, at);
+                return;
+            }
+            _ => {}
+        }
+
+        self.sessions.insert(session_id.to_string(), trace);
+    }
+
+    /// Close sessions with no activity for `idle`, ending spans at their last seen timestamp.
+    pub fn close_idle(&mut self, now: Instant, idle: Duration) {
+        let idle_ids: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|(_, t)| now.duration_since(t.last_activity) >= idle)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in idle_ids {
+            if let Some(trace) = self.sessions.remove(&id) {
+                let end = trace.last_seen_unix_nanos;
+                self.close_session(trace, end);
+            }
+        }
+    }
+
+    pub fn take_finished(&mut self) -> Vec<SpanRecord> {
+        std::mem::take(&mut self.finished)
+    }
+
+    fn start_turn(&mut self, trace: &mut SessionTrace, at: u64) {
+        if let Some(turn) = trace.turn.take() {
+            self.finish(&trace.trace_id, turn, at, None);
+        }
+        trace.turn_count += 1;
+        trace.turn_has_tools = false;
+        let root = Some(trace.root.span_id.clone());
+        trace.turn = Some(self.open_span(
+            "assistant.turn",
+            root,
+            at,
+            vec![("turn.index".to_string(), trace.turn_count.to_string())],
+        ));
+    }
+
+    fn close_session(&mut self, mut trace: SessionTrace, at: u64) {
+        for (_, span) in trace.tools.drain() {
+            self.finish(
+                &trace.trace_id,
+                span,
+                at,
+                Some("no tool result before session ended".to_string()),
+            );
+        }
+        if let Some(turn) = trace.turn.take() {
+            self.finish(&trace.trace_id, turn, at, None);
+        }
+        self.finish(&trace.trace_id, trace.root, at, None);
+    }
+
+    fn open_span(
+        &mut self,
+        name: &str,
+        parent: Option<String>,
+        at: u64,
+        attributes: Vec<(String, String)>,
+    ) -> OpenSpan {
+        self.next_span += 1;
+        OpenSpan {
+            span_id: format!("{:016x}", splitmix64(self.id_seed ^ self.next_span)),
+            parent,
+            name: name.to_string(),
+            start_unix_nanos: at,
+            attributes,
+        }
+    }
+
+    fn finish(&mut self, trace_id: &str, span: OpenSpan, end: u64, error: Option<String>) {
+        self.finished.push(SpanRecord {
+            trace_id: trace_id.to_string(),
+            span_id: span.span_id,
+            parent_span_id: span.parent,
+            name: span.name,
+            start_unix_nanos: span.start_unix_nanos,
+            end_unix_nanos: end.max(span.start_unix_nanos),
+            attributes: span.attributes,
+            error,
+        });
+    }
+}
+
+fn tool_key(entry: &LogEntry) -> String {
+    entry
+        .tool_use_id
+        .clone()
+        .or_else(|| entry.tool_name.clone())
+        .unwrap_or_default()
+}
+
+/// Entry time as Unix nanoseconds
+fn entry_unix_nanos(entry: &LogEntry) -> u64 {
+    entry.timestamp.timestamp_nanos_opt().unwrap_or(0).max(0) as u64
+}
+
+/// Claude session ids are UUIDs, which map 1:1 onto a 128-bit trace id.
+fn trace_id_for_session(session_id: &str, seed: u64) -> String {
+    let hex: String = session_id.chars().filter(|c| *c != '-').collect();
+    if hex.len() == 32 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return hex.to_ascii_lowercase();
+    }
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    session_id.hash(&mut hasher);
+    let h = hasher.finish();
+    format!("{:016x}{:016x}", splitmix64(h ^ seed), splitmix64(h))
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SESSION: &str = "0f8fad5b-d9cb-469f-a165-70867728950e";
+
+    fn entry(entry_type: LogEntryType, ts: &str) -> LogEntry {
+        LogEntry::test(entry_type).session(SESSION).at(ts)
+    }
+
+    #[test]
+    fn test_builds_session_turn_tool_tree() {
+        let mut tracer = SessionTracer::new();
+        let now = Instant::now();
+
+        for e in [
+            entry(LogEntryType::Message, "2025-01-01T00:00:00Z"),
+            entry(LogEntryType::ToolCall, "2025-01-01T00:00:01Z")
+                .tool("Bash")
+                .tool_use_id("t1"),
+            entry(LogEntryType::Error, "2025-01-01T00:00:03Z").tool_use_id("t1"),
+            entry(LogEntryType::SessionEnd, "2025-01-01T00:00:04Z"),
+        ] {
+            tracer.observe(&e, now);
+        }
+
+        let spans = tracer.take_finished();
+        let names: Vec<&str> = spans.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Bash", "assistant.turn", "claude.session"]);
+
+        let (tool, turn, root) = (&spans[0], &spans[1], &spans[2]);
+        assert!(spans
+            .iter()
+            .all(|s| s.trace_id == "0f8fad5bd9cb469fa16570867728950e"));
+        assert_eq!(tool.parent_span_id.as_deref(), Some(turn.span_id.as_str()));
+        assert_eq!(turn.parent_span_id.as_deref(), Some(root.span_id.as_str()));
+        assert!(root.parent_span_id.is_none());
+        assert!(tool.error.is_some());
+        assert_eq!(tool.end_unix_nanos - tool.start_unix_nanos, 2_000_000_000);
+    }
+
+    #[test]
+    fn test_idle_session_is_closed() {
+        let mut tracer = SessionTracer::new();
+        let now = Instant::now();
+        tracer.observe(&entry(LogEntryType::Message, "2025-01-01T00:00:00Z"), now);
+
+        tracer.close_idle(now + Duration::from_secs(1), Duration::from_secs(60));
+        assert!(tracer.take_finished().is_empty());
+
+        tracer.close_idle(now + Duration::from_secs(61), Duration::from_secs(60));
+        assert_eq!(tracer.take_finished().len(), 2);
+    }
+    #[test]
+    fn test_tool_stays_under_the_turn_that_called_it() {
+        let mut tracer = SessionTracer::new();
+        let now = Instant::now();
+        for e in [
+            entry(LogEntryType::Message, "2025-01-01T00:00:00Z"),
+            entry(LogEntryType::ToolCall, "2025-01-01T00:00:01Z")
+                .tool("Bash")
+                .tool_use_id("t1"),
+            // A message before the result starts the next turn.
+            entry(LogEntryType::Message, "2025-01-01T00:00:02Z"),
+            entry(LogEntryType::ToolResult, "2025-01-01T00:00:03Z").tool_use_id("t1"),
+        ] {
+            tracer.observe(&e, now);
+        }
+
+        let spans = tracer.take_finished();
+        let names: Vec<&str> = spans.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["assistant.turn", "Bash"]);
+        assert_eq!(
+            spans[1].parent_span_id.as_deref(),
+            Some(spans[0].span_id.as_str())
+        );
+    }
+}
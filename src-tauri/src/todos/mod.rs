@@ -0,0 +1,151 @@
+use crate::models::{
+    AppEvent, LogEntry, LogEntryType, TodoDiff, TodoItem, TodoStatusChange, ToolInput,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The current TodoWrite plan of one session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTodos {
+    pub session_id: String,
+    pub todos: Vec<TodoItem>,
+}
+
+/// Latest TodoWrite plan per session.
+///
+/// Each TodoWrite call replaces the whole list, so updates are diffed against
+/// the previous list by item content.
+pub struct TodoBoard {
+    sessions: HashMap<String, Vec<TodoItem>>,
+}
+
+impl TodoBoard {
+    pub fn new() -> Self {
+        Self {
+            sessions: HashMap::new(),
+        }
+    }
+
+    /// Apply TodoWrite calls in `entries`; returns one `TodoListUpdate` per plan that changed.
+    pub fn apply(&mut self, entries: &[LogEntry]) -> Vec<AppEvent> {
+        let mut events = Vec::new();
+        for entry in entries {
+            if entry.entry_type != LogEntryType::ToolCall {
+                continue;
+            }
+            let Some(ToolInput::TodoWrite(input)) = &entry.tool_input else {
+                continue;
+            };
+            let session_id = entry.session_id.clone().unwrap_or_default();
+            let previous = self.sessions.get(&session_id).map(Vec::as_slice);
+
+            let diff = diff_todos(previous.unwrap_or_default(), &input.todos);
+            if previous.is_some() && diff == TodoDiff::default() {
+                continue;
+            }
+
+            self.sessions
+                .insert(session_id.clone(), input.todos.clone());
+            events.push(AppEvent::TodoListUpdate {
+                session_id,
+                todos: input.todos.clone(),
+                diff,
+            });
+        }
+        events
+    }
+
+    pub fn snapshot(&self, session_id: Option<&str>) -> Vec<SessionTodos> {
+        let mut sessions: Vec<SessionTodos> = self
+            .sessions
+            .iter()
+            .filter(|(id, _)| session_id.is_none_or(|s| s == id.as_str()))
+            .map(|(id, todos)| SessionTodos {
+                session_id: id.clone(),
+                todos: todos.clone(),
+            })
+            .collect();
+        sessions.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+        sessions
+    }
+}
+
+fn diff_todos(previous: &[TodoItem], current: &[TodoItem]) -> TodoDiff {
+    let find = |items: &[TodoItem], content: &str| -> Option<TodoItem> {
+        items.iter().find(|t| t.content == content).cloned()
+    };
+
+    let mut diff = TodoDiff::default();
+    for item in current {
+        match find(previous, &item.content) {
+            None => diff.added.push(item.clone()),
+            Some(old) if old.status != item.status => diff.status_changed.push(TodoStatusChange {
+                content: item.content.clone(),
+                from: old.status,
+                to: item.status,
+            }),
+            Some(_) => {}
+        }
+    }
+    diff.removed = previous
+        .iter()
+        .filter(|old| find(current, &old.content).is_none())
+        .cloned()
+        .collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TodoStatus;
+    use serde_json::json;
+
+    fn todo_write(todos: serde_json::Value) -> LogEntry {
+        LogEntry::test(LogEntryType::ToolCall)
+            .tool("TodoWrite")
+            .input(json!({ "todos": todos }))
+            .session("s1")
+    }
+
+    #[test]
+    fn test_diffs_consecutive_plans() {
+        let mut board = TodoBoard::new();
+        let first = board.apply(&[todo_write(json!([
+            {"content": "Write tests", "status": "in_progress", "activeForm": "Writing tests"},
+            {"content": "Fix bug", "status": "pending", "activeForm": "Fixing bug"}
+        ]))]);
+        let [AppEvent::TodoListUpdate { diff, .. }] = first.as_slice() else {
+            panic!("expected one update");
+        };
+        assert_eq!(diff.added.len(), 2);
+        assert_eq!(diff.added[0].active_form, "Writing tests");
+
+        let second = board.apply(&[todo_write(json!([
+            {"content": "Write tests", "status": "completed", "activeForm": "Writing tests"},
+            {"content": "Ship it", "status": "pending", "activeForm": "Shipping"}
+        ]))]);
+        let [AppEvent::TodoListUpdate { diff, .. }] = second.as_slice() else {
+            panic!("expected one update");
+        };
+        assert_eq!(diff.added[0].content, "Ship it");
+        assert_eq!(diff.removed[0].content, "Fix bug");
+        assert_eq!(
+            diff.status_changed,
+            vec![TodoStatusChange {
+                content: "Write tests".to_string(),
+                from: TodoStatus::InProgress,
+                to: TodoStatus::Completed,
+            }]
+        );
+        assert_eq!(board.snapshot(Some("s1"))[0].todos.len(), 2);
+    }
+
+    #[test]
+    fn test_unchanged_plan_emits_nothing() {
+        let mut board = TodoBoard::new();
+        let plan = json!([{"content": "A", "status": "pending", "activeForm": "Doing A"}]);
+        assert_eq!(board.apply(&[todo_write(plan.clone())]).len(), 1);
+        assert!(board.apply(&[todo_write(plan)]).is_empty());
+    }
+}
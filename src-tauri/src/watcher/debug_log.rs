@@ -0,0 +1,294 @@
+//! Grammar for `~/.claude/debug/<session>.txt` records:
+//! `<timestamp> [LEVEL] <component> <message>`, optionally followed by
+//! indented continuation lines (stack traces, dumped JSON).
+
+use super::log_parser::parse_timestamp;
+use crate::models::{LogEntryType, TimestampSource};
+use chrono::{DateTime, Utc};
+
+/// Prefixes that name the tool a record is about
+const TOOL_PREFIXES: [(&str, ToolPhase); 4] = [
+    ("Tool call: ", ToolPhase::Call),
+    ("Tool result: ", ToolPhase::Result),
+    ("executePreToolHooks called for tool: ", ToolPhase::Call),
+    ("executePostToolHooks called for tool: ", ToolPhase::Result),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl DebugLevel {
+    fn parse(tag: &str) -> Option<Self> {
+        match tag.to_ascii_uppercase().as_str() {
+            "DEBUG" | "TRACE" | "VERBOSE" => Some(Self::Debug),
+            "INFO" => Some(Self::Info),
+            "WARN" | "WARNING" => Some(Self::Warn),
+            "ERROR" | "FATAL" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolPhase {
+    Call,
+    Result,
+}
+
+/// Subsystem a record comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component<'a> {
+    /// `Tool call: Read`, `executePreToolHooks called for tool: Bash`, ...
+    Tool {
+        phase: ToolPhase,
+        name: &'a str,
+    },
+    /// `MCP server "github": ...`
+    Mcp {
+        server: &'a str,
+    },
+    /// A bracketed tag such as `[SLOW OPERATION DETECTED]`
+    Tag(&'a str),
+    None,
+}
+
+/// One debug log record, borrowed from its text
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugRecord<'a> {
+    pub timestamp: Option<(DateTime<Utc>, TimestampSource)>,
+    pub level: Option<DebugLevel>,
+    pub component: Component<'a>,
+    /// Everything after the timestamp and level, continuation lines included
+    pub message: &'a str,
+}
+
+impl<'a> DebugRecord<'a> {
+    pub fn parse(text: &'a str) -> Option<Self> {
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        let (timestamp, rest) = match extract_timestamp(text) {
+            Some((timestamp, rest)) => (Some(timestamp), rest),
+            None => (None, text),
+        };
+        let (level, message) = split_level(rest);
+        let header = message.lines().next().unwrap_or("");
+        Some(Self {
+            timestamp,
+            level,
+            component: parse_component(header),
+            message,
+        })
+    }
+
+    /// Entry type and tool name, from the level and component only.
+    pub fn classify(&self) -> (LogEntryType, Option<String>) {
+        let tool_name = match self.component {
+            Component::Tool { name, .. } => Some(name.to_string()),
+            _ => None,
+        };
+        let is_error = match self.level {
+            Some(level) => level == DebugLevel::Error,
+            // Unlevelled lines (older logs) mark errors with a leading `Error:`.
+            None => self.message.starts_with("Error:") || self.message.starts_with("error:"),
+        };
+        if is_error {
+            return (LogEntryType::Error, tool_name);
+        }
+        match self.component {
+            Component::Tool {
+                phase: ToolPhase::Call,
+                ..
+            } => (LogEntryType::ToolCall, tool_name),
+            Component::Tool {
+                phase: ToolPhase::Result,
+                ..
+            } => (LogEntryType::ToolResult, tool_name),
+            _ => (LogEntryType::Message, None),
+        }
+    }
+}
+
+/// Join continuation lines onto the timestamped record above them.
+///
+/// Other untimestamped lines (e.g. `Tool call: Read` from older logs) stay
+/// records of their own and close the record above, so logs without timestamps
+/// still parse line by line. A trace split across two reads arrives as separate
+/// records.
+pub fn stitch_records(lines: Vec<String>) -> Vec<String> {
+    let mut records: Vec<String> = Vec::with_capacity(lines.len());
+    let mut open = false;
+    for line in lines {
+        if extract_timestamp(line.trim_start()).is_some() {
+            records.push(line);
+            open = true;
+        } else if let Some(record) = records
+            .last_mut()
+            .filter(|_| open && is_continuation(&line))
+        {
+            record.push('\n');
+            record.push_str(&line);
+        } else {
+            records.push(line);
+            open = false;
+        }
+    }
+    records
+}
+
+/// Indented lines, stack frames and the edges of dumped JSON
+fn is_continuation(line: &str) -> bool {
+    line.starts_with(char::is_whitespace)
+        || line.starts_with("at ")
+        || line.starts_with("Caused by")
+        || line.starts_with(['{', '}', ']'])
+}
+
+/// Leading timestamp of a record and the rest of its text.
+fn extract_timestamp(line: &str) -> Option<((DateTime<Utc>, TimestampSource), &str)> {
+    if !line.chars().take(4).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    // `2025-01-01T12:00:00.123Z [DEBUG] ...`
+    let (token, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    if let Some(parsed) = DateTime::parse_from_rfc3339(token)
+        .ok()
+        .map(|t| (t.with_timezone(&Utc), TimestampSource::Logged))
+    {
+        return Some((parsed, rest.trim()));
+    }
+    // `2025-01-01 12:00:00 ...`; byte offsets only, `get` rejects a cut inside a character.
+    let parsed = parse_timestamp(line.get(..19)?)?;
+    // Skip the separator after the timestamp, whatever its width.
+    let mut rest = line[19..].chars();
+    rest.next();
+    Some((parsed, rest.as_str().trim()))
+}
+
+fn split_level(text: &str) -> (Option<DebugLevel>, &str) {
+    text.strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .and_then(|(tag, rest)| Some((Some(DebugLevel::parse(tag)?), rest.trim_start())))
+        .unwrap_or((None, text))
+}
+
+fn parse_component(header: &str) -> Component<'_> {
+    for (prefix, phase) in TOOL_PREFIXES {
+        if let Some(name) = header
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.split_whitespace().next())
+        {
+            return Component::Tool { phase, name };
+        }
+    }
+    if let Some((server, _)) = header
+        .strip_prefix("MCP server \"")
+        .and_then(|rest| rest.split_once('"'))
+    {
+        return Component::Mcp { server };
+    }
+    if let Some((tag, _)) = header
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+    {
+        return Component::Tag(tag);
+    }
+    Component::None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classifies_by_level_and_component() {
+        let record = DebugRecord::parse(
+            "2025-01-01T00:00:00Z [DEBUG] executePreToolHooks called for tool: Bash",
+        )
+        .unwrap();
+        assert_eq!(record.level, Some(DebugLevel::Debug));
+        assert_eq!(
+            record.classify(),
+            (LogEntryType::ToolCall, Some("Bash".to_string()))
+        );
+
+        let record = DebugRecord::parse(
+            r#"2025-01-01T00:00:00Z [ERROR] MCP server "github": Connection failed"#,
+        )
+        .unwrap();
+        assert_eq!(record.component, Component::Mcp { server: "github" });
+        assert_eq!(record.classify(), (LogEntryType::Error, None));
+
+        let record = DebugRecord::parse(
+            "2025-01-01T00:00:00Z [WARN] [SLOW OPERATION DETECTED] fs.readFileSync",
+        )
+        .unwrap();
+        assert_eq!(record.component, Component::Tag("SLOW OPERATION DETECTED"));
+
+        // Mentions of tools, todos or errors inside a message are not classification.
+        for line in [
+            "2025-01-01T00:00:00Z [DEBUG] Read the todo list from settings",
+            "2025-01-01T00:00:00Z [DEBUG] Retrying after error: timeout",
+        ] {
+            let record = DebugRecord::parse(line).unwrap();
+            assert_eq!(record.classify(), (LogEntryType::Message, None));
+        }
+    }
+
+    #[test]
+    fn test_stitches_continuation_lines() {
+        let lines = [
+            "2025-01-01T00:00:00Z [ERROR] TypeError: x is undefined",
+            "    at run (cli.js:10:5)",
+            "    at main (cli.js:20:1)",
+            "2025-01-01T00:00:01Z [DEBUG] Stream started",
+        ]
+        .map(String::from)
+        .to_vec();
+        let records = stitch_records(lines);
+        assert_eq!(records.len(), 2);
+        let record = DebugRecord::parse(&records[0]).unwrap();
+        assert_eq!(record.classify().0, LogEntryType::Error);
+        assert!(record.message.ends_with("at main (cli.js:20:1)"));
+
+        // Without timestamps there is nothing to continue.
+        let plain = ["Tool call: Read", "  indented"].map(String::from).to_vec();
+        assert_eq!(stitch_records(plain).len(), 2);
+    }
+
+    #[test]
+    fn test_unstamped_records_are_not_continuations() {
+        let lines = [
+            "2025-01-01T00:00:00Z [ERROR] Request failed",
+            "Caused by: connection reset",
+            "Tool call: Bash",
+            "Tool result: Bash",
+            "    at stray (cli.js:1:1)",
+            "2025-01-01T00:00:01Z [DEBUG] Stream started",
+        ]
+        .map(String::from)
+        .to_vec();
+        let records = stitch_records(lines);
+        let classified: Vec<_> = records
+            .iter()
+            .map(|r| DebugRecord::parse(r).unwrap().classify())
+            .collect();
+        assert_eq!(
+            classified,
+            [
+                (LogEntryType::Error, None),
+                (LogEntryType::ToolCall, Some("Bash".to_string())),
+                (LogEntryType::ToolResult, Some("Bash".to_string())),
+                (LogEntryType::Message, None),
+                (LogEntryType::Message, None),
+            ]
+        );
+        assert!(records[0].ends_with("Caused by: connection reset"));
+    }
+}
@@ -1,24 +1,37 @@
-use crate::models::{AgentStatus, AgentType, LogEntry, LogEntryType};
-
-/// Parse a line from a debug log file
+use super::debug_log::DebugRecord;
+use super::tool_detail::tool_detail;
+use crate::models::{AgentStatus, AgentType, LogEntry, LogEntryType, TimestampSource, ToolInput};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Same pattern the frontend uses in `isLimitReachedMessage`.
+static LIMIT_REACHED_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)limit\s*reached|hit\s+your\s+limit|rate[_\s]*limit|429")
+        .expect("valid rate limit regex")
+});
+
+/// Parse one debug log record: a line, plus any continuation lines stitched onto it
 pub fn parse_debug_line(line: &str) -> Option<LogEntry> {
-    let line = line.trim();
-    if line.is_empty() {
-        return None;
-    }
-
-    // Extract timestamp if present (format: YYYY-MM-DD HH:MM:SS)
-    let (timestamp, content) = extract_timestamp(line);
-
-    // Determine entry type
-    let (entry_type, tool_name) = determine_entry_type(content);
+    let record = DebugRecord::parse(line)?;
+    let (entry_type, tool_name) = record.classify();
+    // RFC 3339, or YYYY-MM-DD HH:MM:SS in local time
+    let (timestamp, timestamp_source) = record.timestamp.unwrap_or_else(ingested_now);
 
     Some(LogEntry {
-        timestamp: timestamp.unwrap_or_default(),
+        timestamp,
+        timestamp_source,
         entry_type,
-        content: content.to_string(),
+        content: record.message.to_string(),
         agent_id: None,
         tool_name,
+        tool_use_id: None,
+        session_id: None,
+        project: None,
+        cwd: None,
+        git_branch: None,
+        tool_input: None,
+        tool_detail: None,
     })
 }
 
@@ -31,6 +44,11 @@ pub fn parse_session_line(line: &str) -> Option<LogEntry> {
 
     // Try to parse as JSON
     if let Ok(json) = serde_json::from_str::<serde_json::Value>(line) {
+        // Claude Code transcripts wrap tool calls/results in `message.content` blocks.
+        if let Some(entry) = parse_message_blocks(&json) {
+            return Some(entry);
+        }
+
         let entry_type = match json.get("type").and_then(|v| v.as_str()) {
             Some("tool_use") => LogEntryType::ToolCall,
             Some("tool_result") => LogEntryType::ToolResult,
@@ -48,25 +66,26 @@ pub fn parse_session_line(line: &str) -> Option<LogEntry> {
         let content = json
             .get("content")
             .or_else(|| json.get("message"))
+            .or_else(|| json.pointer("/message/content"))
             .and_then(|v| v.as_str())
             .unwrap_or("")
             .to_string();
 
-        let timestamp = json
-            .get("timestamp")
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string();
-
+        let (timestamp, timestamp_source) = json_timestamp(&json);
         return Some(LogEntry {
             timestamp,
+            timestamp_source,
             entry_type,
             content,
-            agent_id: json
-                .get("agent_id")
-                .and_then(|v| v.as_str())
-                .map(String::from),
+            agent_id: json_agent_id(&json),
             tool_name,
+            tool_use_id: None,
+            session_id: json_session_id(&json),
+            project: None,
+            cwd: json_str(&json, "cwd"),
+            git_branch: json_str(&json, "gitBranch"),
+            tool_input: None,
+            tool_detail: None,
         });
     }
 
@@ -74,6 +93,167 @@ pub fn parse_session_line(line: &str) -> Option<LogEntry> {
     parse_debug_line(line)
 }
 
+/// Parse the first meaningful block of a transcript `message.content` array.
+///
+/// Only one entry is produced per line, so a tool block wins over surrounding text.
+fn parse_message_blocks(json: &serde_json::Value) -> Option<LogEntry> {
+    let blocks = json.pointer("/message/content")?.as_array()?;
+
+    let block = blocks
+        .iter()
+        .find(|b| matches!(block_type(b), Some("tool_use") | Some("tool_result")))
+        .or_else(|| blocks.iter().find(|b| block_type(b) == Some("text")))?;
+
+    let (entry_type, tool_name, content, tool_use_id) = match block_type(block) {
+        Some("tool_use") => (
+            LogEntryType::ToolCall,
+            block.get("name").and_then(|v| v.as_str()).map(String::from),
+            block
+                .get("input")
+                .map(summarize_tool_input)
+                .unwrap_or_default(),
+            block.get("id").and_then(|v| v.as_str()).map(String::from),
+        ),
+        Some("tool_result") => {
+            let is_error = block
+                .get("is_error")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            (
+                if is_error {
+                    LogEntryType::Error
+                } else {
+                    LogEntryType::ToolResult
+                },
+                None,
+                block.get("content").map(content_text).unwrap_or_default(),
+                block
+                    .get("tool_use_id")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+            )
+        }
+        _ => (
+            LogEntryType::Message,
+            None,
+            block
+                .get("text")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string(),
+            None,
+        ),
+    };
+
+    let tool_input = match (&tool_name, block.get("input")) {
+        (Some(name), Some(input)) if entry_type == LogEntryType::ToolCall => {
+            Some(ToolInput::parse(name, input))
+        }
+        _ => None,
+    };
+
+    let (timestamp, timestamp_source) = json_timestamp(json);
+    Some(LogEntry {
+        timestamp,
+        timestamp_source,
+        entry_type,
+        content,
+        agent_id: json_agent_id(json),
+        tool_name,
+        tool_use_id,
+        session_id: json_session_id(json),
+        project: None,
+        cwd: json_str(json, "cwd"),
+        git_branch: json_str(json, "gitBranch"),
+        tool_detail: tool_input.as_ref().and_then(tool_detail),
+        tool_input,
+    })
+}
+
+fn block_type(block: &serde_json::Value) -> Option<&str> {
+    block.get("type").and_then(|v| v.as_str())
+}
+
+fn json_timestamp(json: &serde_json::Value) -> (DateTime<Utc>, TimestampSource) {
+    json.get("timestamp")
+        .and_then(|v| v.as_str())
+        .and_then(parse_timestamp)
+        .unwrap_or_else(ingested_now)
+}
+
+/// Parse an RFC 3339 time, or a naive `YYYY-MM-DD[ T]HH:MM:SS[.f]` in the machine's local zone.
+pub fn parse_timestamp(raw: &str) -> Option<(DateTime<Utc>, TimestampSource)> {
+    if let Ok(t) = DateTime::parse_from_rfc3339(raw) {
+        return Some((t.with_timezone(&Utc), TimestampSource::Logged));
+    }
+    let naive = NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f"))
+        .ok()?;
+    // `earliest` picks the first reading of an ambiguous DST hour; skipped hours fail.
+    let local = Local.from_local_datetime(&naive).earliest()?;
+    Some((local.with_timezone(&Utc), TimestampSource::LocalTime))
+}
+
+/// Fallback for entries that carry no time of their own
+fn ingested_now() -> (DateTime<Utc>, TimestampSource) {
+    (Utc::now(), TimestampSource::Ingested)
+}
+
+fn json_agent_id(json: &serde_json::Value) -> Option<String> {
+    json.get("agent_id")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+}
+
+fn json_str(json: &serde_json::Value, key: &str) -> Option<String> {
+    json.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+fn json_session_id(json: &serde_json::Value) -> Option<String> {
+    json.get("sessionId")
+        .or_else(|| json.get("session_id"))
+        .and_then(|v| v.as_str())
+        .map(String::from)
+}
+
+/// Flatten a tool result's content (plain string or array of text blocks).
+fn content_text(content: &serde_json::Value) -> String {
+    match content {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(parts) => parts
+            .iter()
+            .filter_map(|p| p.get("text").and_then(|v| v.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// One-line summary of a tool's input, used as the entry content.
+///
+/// Prefers the field that identifies the work (command, path, pattern...) so that
+/// Bash classification and hook/transcript deduplication see the same text.
+pub fn summarize_tool_input(input: &serde_json::Value) -> String {
+    const SUMMARY_KEYS: [&str; 8] = [
+        "command",
+        "file_path",
+        "notebook_path",
+        "pattern",
+        "url",
+        "query",
+        "description",
+        "prompt",
+    ];
+
+    let summary = SUMMARY_KEYS
+        .iter()
+        .find_map(|key| input.get(key).and_then(|v| v.as_str()))
+        .map(String::from)
+        .unwrap_or_else(|| input.to_string());
+
+    summary.chars().take(500).collect()
+}
+
 /// Determine which agent type should handle this log entry
 ///
 /// Workflow-based mapping:
@@ -157,59 +337,9 @@ pub fn determine_agent_status(entry: &LogEntry) -> AgentStatus {
     }
 }
 
-fn extract_timestamp(line: &str) -> (Option<String>, &str) {
-    // Simple timestamp extraction - looks for ISO-like format at the start
-    if line.len() >= 19 && line.chars().take(4).all(|c| c.is_ascii_digit()) {
-        let potential_ts = &line[..19];
-        if potential_ts.contains('-') && (potential_ts.contains(':') || potential_ts.contains('T'))
-        {
-            return (Some(potential_ts.to_string()), line[20..].trim());
-        }
-    }
-    (None, line)
-}
-
-fn determine_entry_type(content: &str) -> (LogEntryType, Option<String>) {
-    // Fast path: avoid regex for tool call/result lines.
-    if let Some(rest) = content.strip_prefix("Tool call: ") {
-        let tool_name = rest.split_whitespace().next().map(|s| s.to_string());
-        return (LogEntryType::ToolCall, tool_name);
-    }
-    if let Some(rest) = content.strip_prefix("Tool result: ") {
-        let tool_name = rest.split_whitespace().next().map(|s| s.to_string());
-        return (LogEntryType::ToolResult, tool_name);
-    }
-
-    // Check for errors
-    if content.contains("[ERROR]")
-        || content.contains("[error]")
-        || content.contains("Error:")
-        || content.contains("error:")
-    {
-        return (LogEntryType::Error, None);
-    }
-
-    // Check for todo updates
-    if content.contains("TodoWrite")
-        || content.contains("Task:")
-        || content.contains("todo")
-        || content.contains("TODO")
-    {
-        return (LogEntryType::TodoUpdate, Some("TodoWrite".to_string()));
-    }
-
-    // Check for tool names in content
-    let known_tools = [
-        "Read", "Write", "Edit", "Glob", "Grep", "Bash", "WebSearch", "WebFetch", "Task",
-        "TodoWrite",
-    ];
-    for tool in known_tools {
-        if content.contains(tool) {
-            return (LogEntryType::ToolCall, Some(tool.to_string()));
-        }
-    }
-
-    (LogEntryType::Message, None)
+/// Check if a message indicates the usage/rate limit has been reached
+pub fn is_limit_reached_message(content: &str) -> bool {
+    LIMIT_REACHED_PATTERN.is_match(content)
 }
 
 #[cfg(test)]
@@ -224,14 +354,55 @@ mod tests {
         assert_eq!(entry.tool_name, Some("Read".to_string()));
     }
 
+    #[test]
+    fn test_parse_debug_line_multibyte_near_timestamp() {
+        for line in [
+            "2025-01-01 00:00:0é rest",
+            "2025-01-01 00:00:00é rest",
+            "2025-01-01 00:00:00",
+            "2025-01-01T00:00:00",
+            "2025💥",
+        ] {
+            parse_debug_line(line).unwrap();
+        }
+        let entry = parse_debug_line("2025-01-01 00:00:00é Tool call: Read").unwrap();
+        assert_eq!(entry.timestamp_source, TimestampSource::LocalTime);
+        assert_eq!(entry.tool_name.as_deref(), Some("Read"));
+    }
+
+    #[test]
+    fn test_timestamps_are_normalized_to_utc() {
+        let entry = parse_debug_line("2025-01-01T09:30:00.250+09:00 Tool call: Read").unwrap();
+        assert_eq!(
+            entry.timestamp.to_rfc3339(),
+            "2025-01-01T00:30:00.250+00:00"
+        );
+        assert_eq!(entry.timestamp_source, TimestampSource::Logged);
+        assert_eq!(entry.tool_name.as_deref(), Some("Read"));
+
+        let naive =
+            NaiveDateTime::parse_from_str("2025-06-01 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let entry = parse_debug_line("2025-06-01 12:00:00 Tool call: Grep").unwrap();
+        assert_eq!(
+            entry.timestamp,
+            Local
+                .from_local_datetime(&naive)
+                .unwrap()
+                .with_timezone(&Utc)
+        );
+
+        let entry = parse_session_line(r#"{"type":"user","message":{"content":"hi"}}"#).unwrap();
+        assert_eq!(entry.timestamp_source, TimestampSource::Ingested);
+        let entry = parse_debug_line("Tool call: Read").unwrap();
+        assert_eq!(entry.timestamp_source, TimestampSource::Ingested);
+    }
+
     #[test]
     fn test_determine_agent_type_analyzer() {
         let entry = LogEntry {
-            timestamp: String::new(),
             entry_type: LogEntryType::ToolCall,
-            content: String::new(),
-            agent_id: None,
             tool_name: Some("Grep".to_string()),
+            ..Default::default()
         };
         assert_eq!(determine_agent_type(&entry), AgentType::Analyzer);
     }
@@ -239,11 +410,9 @@ mod tests {
     #[test]
     fn test_determine_agent_type_explorer() {
         let entry = LogEntry {
-            timestamp: String::new(),
             entry_type: LogEntryType::ToolCall,
-            content: String::new(),
-            agent_id: None,
             tool_name: Some("Read".to_string()),
+            ..Default::default()
         };
         assert_eq!(determine_agent_type(&entry), AgentType::Explorer);
     }
@@ -251,12 +420,32 @@ mod tests {
     #[test]
     fn test_determine_agent_type_connector() {
         let entry = LogEntry {
-            timestamp: String::new(),
             entry_type: LogEntryType::ToolCall,
-            content: String::new(),
-            agent_id: None,
             tool_name: Some("mcp__chrome-devtools__click".to_string()),
+            ..Default::default()
         };
         assert_eq!(determine_agent_type(&entry), AgentType::Connector);
     }
+
+    #[test]
+    fn test_parse_transcript_tool_use() {
+        let line = r#"{"type":"assistant","sessionId":"abc","timestamp":"2025-01-01T00:00:00Z","message":{"role":"assistant","content":[{"type":"text","text":"Running tests"},{"type":"tool_use","id":"toolu_01","name":"Bash","input":{"command":"cargo test"}}]}}"#;
+        let entry = parse_session_line(line).unwrap();
+        assert_eq!(entry.entry_type, LogEntryType::ToolCall);
+        assert_eq!(entry.tool_name.as_deref(), Some("Bash"));
+        assert_eq!(entry.content, "cargo test");
+        assert_eq!(entry.tool_use_id.as_deref(), Some("toolu_01"));
+        assert_eq!(entry.session_id.as_deref(), Some("abc"));
+        assert!(matches!(entry.tool_input, Some(ToolInput::Bash(_))));
+        assert_eq!(determine_agent_type(&entry), AgentType::Validator);
+    }
+
+    #[test]
+    fn test_parse_transcript_tool_result() {
+        let line = r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"toolu_01","content":"ok"}]}}"#;
+        let entry = parse_session_line(line).unwrap();
+        assert_eq!(entry.entry_type, LogEntryType::ToolResult);
+        assert_eq!(entry.content, "ok");
+        assert_eq!(entry.tool_use_id.as_deref(), Some("toolu_01"));
+    }
 }
@@ -1,33 +1,40 @@
-use crate::models::{Agent, AppEvent, LogEntry, LogEntryType};
-use crate::watcher::log_parser::{
-    determine_agent_status, determine_agent_type, parse_debug_line, parse_session_line,
-};
+use crate::activity::FileActivityTracker;
+use crate::events::{self, BatchEmitter, Flush};
+use crate::filters::EntryFilter;
+use crate::ingest::{Deduplicator, EntrySource};
+use crate::metrics::Metrics;
+use crate::models::{AppEvent, LogEntry, TimestampSource};
+use crate::notifications::NotificationCenter;
+use crate::office::OfficeState;
+use crate::projects::{decode_project_dir, project_id_for};
+use crate::redact::Redactor;
+use crate::settings::SettingsStore;
+use crate::telemetry::TraceExporter;
+use crate::todos::TodoBoard;
+use crate::watcher::log_parser::{parse_debug_line, parse_session_line};
+use crate::watcher::pool;
+use crate::watcher::stitch_records;
+use chrono::{DateTime, Utc};
 use notify_debouncer_full::{new_debouncer, notify::RecursiveMode, DebouncedEvent};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader, Seek, SeekFrom};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
-use tauri::{AppHandle, Emitter};
-
-/// File position tracker for reading new content only
-struct FileTracker {
-    positions: HashMap<PathBuf, u64>,
-    /// Buffer for a trailing line fragment when a file does not end with '\n'.
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+/// Read position of one watched file
+#[derive(Default)]
+struct FileCursor {
+    position: u64,
+    /// Trailing line fragment when the file does not end with '\n'.
     /// This prevents losing/duplicating content across incremental reads.
-    partial_lines: HashMap<PathBuf, String>,
+    partial_line: String,
 }
 
-impl FileTracker {
-    fn new() -> Self {
-        Self {
-            positions: HashMap::new(),
-            partial_lines: HashMap::new(),
-        }
-    }
-
-    fn read_new_lines(&mut self, path: &PathBuf) -> Vec<String> {
+impl FileCursor {
+    fn read_new_lines(&mut self, path: &Path) -> Vec<String> {
         let mut lines: Vec<String> = Vec::new();
 
         let Ok(metadata) = std::fs::metadata(path) else {
@@ -35,7 +42,7 @@ impl FileTracker {
         };
         let file_len = metadata.len();
 
-        let mut pos = self.positions.get(path).copied().unwrap_or(0);
+        let mut pos = self.position;
         let original_pos = pos;
 
         // If the file was truncated/rotated, reset to start and drop any partial tail.
@@ -47,8 +54,8 @@ impl FileTracker {
                 file_len
             );
             pos = 0;
-            self.positions.insert(path.clone(), 0);
-            self.partial_lines.remove(path);
+            self.position = 0;
+            self.partial_line.clear();
         }
 
         let Ok(mut file) = File::open(path) else {
@@ -60,7 +67,7 @@ impl FileTracker {
         }
 
         // Carry over the previous trailing fragment (no newline at EOF).
-        let mut carry = self.partial_lines.remove(path).unwrap_or_default();
+        let mut carry = std::mem::take(&mut self.partial_line);
 
         let mut reader = BufReader::new(file);
         let mut buf = String::new();
@@ -101,17 +108,41 @@ impl FileTracker {
                     new_pos
                 );
             }
-            self.positions.insert(path.clone(), new_pos);
+            self.position = new_pos;
         }
 
         if !carry.is_empty() {
-            self.partial_lines.insert(path.clone(), carry);
+            self.partial_line = carry;
         }
 
         lines
     }
 }
 
+/// File position tracker for reading new content only.
+///
+/// Cursors are checked out for the duration of a read so files can be read
+/// concurrently without holding the tracker lock.
+struct FileTracker {
+    cursors: HashMap<PathBuf, FileCursor>,
+}
+
+impl FileTracker {
+    fn new() -> Self {
+        Self {
+            cursors: HashMap::new(),
+        }
+    }
+
+    fn checkout(&mut self, path: &Path) -> FileCursor {
+        self.cursors.remove(path).unwrap_or_default()
+    }
+
+    fn checkin(&mut self, path: PathBuf, cursor: FileCursor) {
+        self.cursors.insert(path, cursor);
+    }
+}
+
 /// Start watching Claude Code log files
 pub fn start_watching(app: AppHandle) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let claude_home = dirs::home_dir()
@@ -127,8 +158,8 @@ pub fn start_watching(app: AppHandle) -> Result<(), Box<dyn std::error::Error +
     let projects_dir = claude_home.join("projects");
 
     // Emit initial status
-    let _ = app.emit(
-        "app-event",
+    events::emit(
+        &app,
         AppEvent::WatcherStatus {
             active: true,
             path: claude_home.to_string_lossy().to_string(),
@@ -162,9 +193,17 @@ pub fn start_watching(app: AppHandle) -> Result<(), Box<dyn std::error::Error +
     loop {
         match rx.recv() {
             Ok(Ok(events)) => {
-                for event in events {
-                    process_event(&event, &app_handle, &file_tracker);
+                // A debounced batch can name the same file many times; read each once.
+                let mut paths: Vec<PathBuf> = Vec::new();
+                for path in events
+                    .into_iter()
+                    .flat_map(|e: DebouncedEvent| e.event.paths)
+                {
+                    if !paths.contains(&path) {
+                        paths.push(path);
+                    }
                 }
+                process_paths(paths, &app_handle, &file_tracker, &projects_dir);
             }
             Ok(Err(errors)) => {
                 for error in errors {
@@ -181,112 +220,257 @@ pub fn start_watching(app: AppHandle) -> Result<(), Box<dyn std::error::Error +
     Ok(())
 }
 
-fn process_event(event: &DebouncedEvent, app: &AppHandle, tracker: &Arc<Mutex<FileTracker>>) {
-    let started_at = std::time::Instant::now();
-    // Collect all logs and agents for batch emit
-    let mut all_logs: Vec<LogEntry> = Vec::new();
-    // Deduplicate agent updates within a batch to reduce IPC payload.
-    let mut agents_by_id: HashMap<String, Agent> = HashMap::new();
-    let mut total_lines_read: usize = 0;
-    let mut total_entries_parsed: usize = 0;
+/// Read changed files concurrently, parse their new lines on the worker pool
+/// and emit the entries merged in timestamp order.
+fn process_paths(
+    paths: Vec<PathBuf>,
+    app: &AppHandle,
+    tracker: &Arc<Mutex<FileTracker>>,
+    projects_dir: &Path,
+) {
+    let started_at = Instant::now();
+    let path_count = paths.len();
+    // Only process .txt, .jsonl and .json files
+    let paths: Vec<PathBuf> = paths
+        .into_iter()
+        .filter(|path| {
+            let ext = path.extension().and_then(|e| e.to_str());
+            matches!(ext, Some("txt") | Some("jsonl") | Some("json")) && path.is_file()
+        })
+        .collect();
+    if paths.is_empty() {
+        return;
+    }
 
-    for path in &event.paths {
-        // Only process .txt and .jsonl files
-        let ext = path.extension().and_then(|e| e.to_str());
-        if !matches!(ext, Some("txt") | Some("jsonl") | Some("json")) {
-            continue;
+    let workers = pool::worker_count();
+    let cursors: Vec<(PathBuf, FileCursor)> = {
+        let mut tracker = tracker
+            .lock()
+            .expect("FileTracker mutex poisoned in watcher thread");
+        paths
+            .into_iter()
+            .map(|path| {
+                let cursor = tracker.checkout(&path);
+                (path, cursor)
+            })
+            .collect()
+    };
+    let reads = pool::parallel_map(cursors, workers, |(path, mut cursor)| {
+        let lines = cursor.read_new_lines(&path);
+        (path, cursor, lines)
+    });
+
+    let project_settings = app
+        .try_state::<SettingsStore>()
+        .map(|s| s.get().projects)
+        .unwrap_or_default();
+    let filter_state = app.try_state::<Mutex<EntryFilter>>();
+    let filter = filter_state
+        .as_ref()
+        .map(|f| f.lock().expect("EntryFilter mutex poisoned"));
+
+    let mut files: Vec<(PathBuf, Option<String>)> = Vec::new();
+    let mut to_parse: Vec<(Vec<String>, pool::LineParser)> = Vec::new();
+    let mut lines_read: Vec<usize> = Vec::new();
+    let mut total_lines_read: usize = 0;
+    {
+        let mut tracker = tracker
+            .lock()
+            .expect("FileTracker mutex poisoned in watcher thread");
+        for (path, cursor, lines) in reads {
+            tracker.checkin(path.clone(), cursor);
+
+            // Still read filtered files so re-enabling them doesn't replay their backlog.
+            let project_id = project_id_for(projects_dir, &path);
+            if project_id
+                .as_deref()
+                .is_some_and(|id| !project_settings.is_watched(id))
+                || filter.as_ref().is_some_and(|f| f.skips_file(&path))
+            {
+                continue;
+            }
+            total_lines_read += lines.len();
+            lines_read.push(lines.len());
+            // Debug logs continue a record (e.g. a stack trace) on untimestamped lines.
+            let (lines, parse): (Vec<String>, pool::LineParser) =
+                match path.extension().and_then(|e| e.to_str()) {
+                    Some("jsonl") | Some("json") => (lines, parse_session_line),
+                    _ => (stitch_records(lines), parse_debug_line),
+                };
+            files.push((path, project_id.as_deref().map(decode_project_dir)));
+            to_parse.push((lines, parse));
         }
+    }
 
-        if !path.is_file() {
-            continue;
+    let parsed = pool::parse_files(to_parse, workers);
+    let mut per_file: Vec<Vec<LogEntry>> = Vec::with_capacity(parsed.len());
+    for (((path, project), entries), lines_read) in files.iter().zip(parsed).zip(lines_read) {
+        // Transcripts and debug logs are both named after the session id.
+        let file_session = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        // Lines without a time of their own fall back to when the file was written.
+        let modified = std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(DateTime::<Utc>::from);
+        let entries: Vec<LogEntry> = entries
+            .into_iter()
+            .filter_map(|mut entry| {
+                if entry.session_id.is_none() {
+                    entry.session_id = Some(file_session.to_string());
+                }
+                if let Some(modified) = modified.filter(|_| !entry.timestamp_source.is_logged()) {
+                    entry.timestamp = modified;
+                    entry.timestamp_source = TimestampSource::FileModified;
+                }
+                entry.project = project.clone();
+                filter
+                    .as_ref()
+                    .is_none_or(|f| f.allows(&entry, Some(path)))
+                    .then_some(entry)
+            })
+            .collect();
+
+        if let Some(metrics) = app.try_state::<Arc<Metrics>>() {
+            metrics.record_file_read(file_session, lines_read, entries.len());
         }
+        per_file.push(entries);
+    }
+    drop(filter);
+
+    let all_logs = pool::merge_by_timestamp(per_file);
+    tracing::debug!(
+        "Watcher event: paths={}, lines_read={}, entries_parsed={}, workers={}",
+        path_count,
+        total_lines_read,
+        all_logs.len(),
+        workers
+    );
+    emit_entries(app, all_logs, EntrySource::File, started_at);
+}
 
-        let new_lines = {
-            let mut tracker = tracker
-                .lock()
-                .expect("FileTracker mutex poisoned in watcher thread");
-            tracker.read_new_lines(path)
-        };
-        total_lines_read += new_lines.len();
-
-        for line in new_lines {
-            let entry = if ext == Some("jsonl") || ext == Some("json") {
-                parse_session_line(&line)
-            } else {
-                parse_debug_line(&line)
-            };
-
-            if let Some(entry) = entry {
-                total_entries_parsed += 1;
-
-                // Collect agent update (dedup within batch).
-                let agent_type = determine_agent_type(&entry);
-                let status = determine_agent_status(&entry);
-
-                let agent = Agent {
-                    id: agent_id_for_type(agent_type).to_string(),
-                    agent_type,
-                    status,
-                    current_task: Some(summarize_current_task(&entry)),
-                    desk_position: get_desk_position(agent_type),
-                };
+/// Classify parsed entries into agent updates and queue them for the next BatchUpdate flush.
+///
+/// Shared by the file watcher and the hook ingest endpoint so both feed the same pipeline.
+pub fn emit_entries(
+    app: &AppHandle,
+    entries: Vec<LogEntry>,
+    source: EntrySource,
+    started_at: Instant,
+) {
+    let parsed = entries.len();
+    let mut all_logs = match app.try_state::<Mutex<Deduplicator>>() {
+        Some(dedup) => dedup
+            .lock()
+            .expect("Deduplicator mutex poisoned")
+            .retain_new(entries, source, started_at),
+        None => entries,
+    };
 
-                agents_by_id.insert(agent.id.clone(), agent);
+    // Emit single batch update instead of individual events
+    if all_logs.is_empty() {
+        return;
+    }
 
-                // Collect log entry (move, no clone).
-                all_logs.push(entry);
-            }
+    // Mask secrets before anything leaves the backend
+    if let Some(redactor) = app.try_state::<Mutex<Redactor>>() {
+        let redactor = redactor.lock().expect("Redactor mutex poisoned");
+        for entry in &mut all_logs {
+            redactor.redact_entry(entry);
         }
     }
 
-    // Emit single batch update instead of individual events
-    if !all_logs.is_empty() {
-        let agents: Vec<Agent> = agents_by_id.into_values().collect();
-        tracing::debug!(
-            "BatchUpdate emit: paths={}, lines_read={}, entries_parsed={}, logs={}, agents={}, elapsed_ms={}",
-            event.paths.len(),
-            total_lines_read,
-            total_entries_parsed,
-            all_logs.len(),
-            agents.len(),
-            started_at.elapsed().as_millis()
-        );
-        let _ = app.emit(
-            "app-event",
-            AppEvent::BatchUpdate {
-                logs: all_logs,
-                agents,
-            },
-        );
+    if let Some(notifications) = app.try_state::<NotificationCenter>() {
+        notifications.observe(&all_logs);
+    }
+    if let Some(metrics) = app.try_state::<Arc<Metrics>>() {
+        metrics.observe_entries(&all_logs, started_at);
+    }
+    if let Some(files) = app.try_state::<Mutex<FileActivityTracker>>() {
+        files
+            .lock()
+            .expect("FileActivityTracker mutex poisoned")
+            .observe(&all_logs);
+    }
+    if let Some(traces) = app.try_state::<TraceExporter>() {
+        traces.observe(&all_logs);
     }
-}
 
-fn summarize_current_task(entry: &LogEntry) -> String {
-    let summary: String = match entry.entry_type {
-        LogEntryType::ToolCall | LogEntryType::ToolResult => {
-            let prefix = if entry.entry_type == LogEntryType::ToolCall {
-                "Tool call"
-            } else {
-                "Tool result"
-            };
-            entry
-                .tool_name
-                .as_deref()
-                .map_or_else(|| prefix.to_string(), |name| format!("{prefix}: {name}"))
+    // Only agents whose state actually changed are sent.
+    let office = app
+        .try_state::<Mutex<OfficeState>>()
+        .map(|office| {
+            office
+                .lock()
+                .expect("OfficeState mutex poisoned")
+                .apply(&all_logs, Instant::now())
+        })
+        .unwrap_or_default();
+    let todo_updates = app
+        .try_state::<Mutex<TodoBoard>>()
+        .map(|board| {
+            board
+                .lock()
+                .expect("TodoBoard mutex poisoned")
+                .apply(&all_logs)
+        })
+        .unwrap_or_default();
+    tracing::debug!(
+        "BatchUpdate queued: source={:?}, entries_parsed={}, logs={}, agents={}, elapsed_ms={}",
+        source,
+        parsed,
+        all_logs.len(),
+        office.agents.len(),
+        started_at.elapsed().as_millis()
+    );
+    let followups: Vec<AppEvent> = office
+        .handoffs
+        .into_iter()
+        .map(AppEvent::Handoff)
+        .chain(todo_updates)
+        .collect();
+    match app.try_state::<Mutex<BatchEmitter>>() {
+        Some(emitter) => emitter.lock().expect("BatchEmitter mutex poisoned").push(
+            all_logs,
+            office.agents,
+            followups,
+            started_at,
+        ),
+        None => {
+            let mut emitter = BatchEmitter::new();
+            emitter.push(all_logs, office.agents, followups, started_at);
+            emit_flush(app, emitter.take());
         }
-        LogEntryType::TodoUpdate => "Todo update".to_string(),
-        LogEntryType::SessionStart => "Session start".to_string(),
-        LogEntryType::SessionEnd => "Session end".to_string(),
-        LogEntryType::Error => "Error".to_string(),
-        // Avoid cloning the full message; keep only a short preview.
-        LogEntryType::Message => entry.content.chars().take(200).collect(),
-    };
+    }
+}
 
-    // Hard cap to keep payload small even if other branches produce longer strings.
-    summary.chars().take(200).collect()
+/// Emit whatever the batch emitter coalesced since the last flush.
+pub fn flush_batches(app: &AppHandle) {
+    let flush = app
+        .try_state::<Mutex<BatchEmitter>>()
+        .and_then(|emitter| emitter.lock().expect("BatchEmitter mutex poisoned").take());
+    emit_flush(app, flush);
 }
 
-fn agent_id_for_type(agent_type: crate::models::AgentType) -> &'static str {
+fn emit_flush(app: &AppHandle, flush: Option<Flush>) {
+    let Some(flush) = flush else {
+        return;
+    };
+    if flush.dropped > 0 {
+        tracing::warn!(
+            "Skipped {} message entries to keep up with the log backlog",
+            flush.dropped
+        );
+    }
+    for event in flush.events {
+        events::emit(app, event);
+    }
+    if let Some(metrics) = app.try_state::<Arc<Metrics>>() {
+        metrics.record_dropped(flush.dropped);
+        metrics.observe_batch_emit(flush.started_at.elapsed());
+    }
+}
+
+pub fn agent_id_for_type(agent_type: crate::models::AgentType) -> &'static str {
     use crate::models::AgentType;
     match agent_type {
         AgentType::Explorer => "explorer",
@@ -299,24 +483,3 @@ fn agent_id_for_type(agent_type: crate::models::AgentType) -> &'static str {
         AgentType::Liaison => "liaison",
     }
 }
-
-/// Desk positions matching TypeScript DESK_CONFIGS (workflow-based).
-/// Note: These values are currently unused by frontend (which uses its own DESK_CONFIGS),
-/// but kept for API consistency.
-fn get_desk_position(agent_type: crate::models::AgentType) -> (f32, f32) {
-    use crate::models::AgentType;
-    // Layout: 3-3-2 vertical arrangement (workflow-based)
-    // Section A (Y=130): Explorer, Analyzer, Architect
-    // Section B (Y=320): Developer, Operator, Validator
-    // Section C (Y=520): Connector, Liaison
-    match agent_type {
-        AgentType::Explorer => (60.0, 130.0),
-        AgentType::Analyzer => (150.0, 130.0),
-        AgentType::Architect => (240.0, 130.0),
-        AgentType::Developer => (60.0, 320.0),
-        AgentType::Operator => (150.0, 320.0),
-        AgentType::Validator => (240.0, 320.0),
-        AgentType::Connector => (60.0, 520.0),
-        AgentType::Liaison => (150.0, 520.0),
-    }
-}
@@ -1,4 +1,14 @@
+mod debug_log;
 mod log_parser;
 mod log_watcher;
+mod pool;
+mod tool_detail;
 
-pub use log_watcher::start_watching;
+pub use debug_log::stitch_records;
+pub use log_parser::{
+    determine_agent_status, determine_agent_type, is_limit_reached_message, parse_debug_line,
+    parse_session_line, summarize_tool_input,
+};
+pub use log_watcher::{agent_id_for_type, emit_entries, flush_batches, start_watching};
+pub use pool::{merge_by_timestamp, parse_files, worker_count, LineParser};
+pub use tool_detail::tool_detail;
@@ -0,0 +1,153 @@
+//! Worker pool for reading and parsing many log files at once (backfill, imports).
+
+use crate::models::LogEntry;
+use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::sync::Mutex;
+
+/// Upper bound on worker threads, whatever the core count
+const MAX_WORKERS: usize = 8;
+/// Lines parsed per work item, so one huge transcript is spread across workers
+const PARSE_CHUNK_LINES: usize = 2048;
+
+/// `parse_session_line` or `parse_debug_line`
+pub type LineParser = fn(&str) -> Option<LogEntry>;
+
+pub fn worker_count() -> usize {
+    std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(MAX_WORKERS)
+}
+
+/// Apply `f` to every item on up to `workers` scoped threads, keeping input order.
+pub fn parallel_map<T, R, F>(items: Vec<T>, workers: usize, f: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let len = items.len();
+    let workers = workers.min(len);
+    if workers <= 1 {
+        return items.into_iter().map(f).collect();
+    }
+
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let results = Mutex::new(Vec::with_capacity(len));
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let Some((index, item)) = queue.lock().expect("pool queue poisoned").next() else {
+                    break;
+                };
+                let result = f(item);
+                results
+                    .lock()
+                    .expect("pool results poisoned")
+                    .push((index, result));
+            });
+        }
+    });
+
+    let mut results = results.into_inner().expect("pool results poisoned");
+    results.sort_unstable_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Parse each file's lines with its parser, in chunks across `workers` threads.
+/// Returns one entry list per file, in line order.
+pub fn parse_files(files: Vec<(Vec<String>, LineParser)>, workers: usize) -> Vec<Vec<LogEntry>> {
+    let file_count = files.len();
+    let mut chunks = Vec::new();
+    for (file, (lines, parse)) in files.into_iter().enumerate() {
+        let mut lines = lines.into_iter().peekable();
+        while lines.peek().is_some() {
+            let chunk: Vec<String> = lines.by_ref().take(PARSE_CHUNK_LINES).collect();
+            chunks.push((file, chunk, parse));
+        }
+    }
+
+    let parsed = parallel_map(chunks, workers, |(file, chunk, parse)| {
+        let entries: Vec<LogEntry> = chunk.iter().filter_map(|line| parse(line)).collect();
+        (file, entries)
+    });
+
+    let mut per_file: Vec<Vec<LogEntry>> = (0..file_count).map(|_| Vec::new()).collect();
+    for (file, entries) in parsed {
+        per_file[file].extend(entries);
+    }
+    per_file
+}
+
+/// Merge per-file entry lists into timestamp order, keeping each file's own order.
+///
+/// An entry whose time was not logged sorts with the entry before it in the same file.
+pub fn merge_by_timestamp(files: Vec<Vec<LogEntry>>) -> Vec<LogEntry> {
+    if files.len() <= 1 {
+        return files.into_iter().flatten().collect();
+    }
+
+    let total = files.iter().map(Vec::len).sum();
+    let mut iters: Vec<_> = files
+        .into_iter()
+        .map(|f| f.into_iter().peekable())
+        .collect();
+    let mut last_keys: Vec<Option<DateTime<Utc>>> = vec![None; iters.len()];
+    let mut heap = BinaryHeap::new();
+    let key_of = |entry: &LogEntry, last: Option<DateTime<Utc>>| match last {
+        Some(last) if !entry.timestamp_source.is_logged() => last,
+        _ => entry.timestamp,
+    };
+    for (file, iter) in iters.iter_mut().enumerate() {
+        if let Some(entry) = iter.peek() {
+            heap.push(Reverse((key_of(entry, None), file)));
+        }
+    }
+
+    let mut merged = Vec::with_capacity(total);
+    while let Some(Reverse((key, file))) = heap.pop() {
+        let entry = iters[file].next().expect("peeked entry");
+        last_keys[file] = Some(key);
+        merged.push(entry);
+        if let Some(next) = iters[file].peek() {
+            heap.push(Reverse((key_of(next, last_keys[file]), file)));
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::LogEntryType;
+
+    fn entry(timestamp: &str, content: &str) -> LogEntry {
+        LogEntry::test(LogEntryType::Message)
+            .at(timestamp)
+            .content(content)
+    }
+
+    #[test]
+    fn test_parallel_map_keeps_order() {
+        let squares = parallel_map((0..1000u64).collect(), 4, |n| n * n);
+        assert_eq!(squares, (0..1000u64).map(|n| n * n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_merge_by_timestamp_interleaves_files() {
+        let merged = merge_by_timestamp(vec![
+            vec![
+                entry("2025-01-01T00:00:01Z", "a1"),
+                LogEntry::test(LogEntryType::Message).content("a1-cont"),
+                entry("2025-01-01T00:00:04Z", "a4"),
+            ],
+            vec![
+                entry("2025-01-01T00:00:02+00:00", "b2"),
+                entry("2025-01-01T00:00:03Z", "b3"),
+            ],
+        ]);
+        let order: Vec<&str> = merged.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(order, ["a1", "a1-cont", "b2", "b3", "a4"]);
+    }
+}
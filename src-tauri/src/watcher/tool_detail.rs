@@ -0,0 +1,117 @@
+use crate::models::{ToolDetail, ToolInput};
+use similar::{ChangeTag, TextDiff};
+
+/// Diff lines kept in the payload; the Developer monitor only shows a few anyway
+const MAX_DIFF_LINES: usize = 60;
+const MAX_DIFF_BYTES: usize = 4 * 1024;
+/// Unchanged lines shown around each change
+const CONTEXT_LINES: usize = 2;
+
+/// Build a diff preview for Edit, MultiEdit and Write calls.
+///
+/// Write has no "before" in the transcript, so it diffs against an empty file.
+pub fn tool_detail(input: &ToolInput) -> Option<ToolDetail> {
+    let (file_path, pairs): (&str, Vec<(&str, &str)>) = match input {
+        ToolInput::Edit(edit) => (
+            &edit.file_path,
+            vec![(edit.old_string.as_str(), edit.new_string.as_str())],
+        ),
+        ToolInput::MultiEdit(multi) => (
+            &multi.file_path,
+            multi
+                .edits
+                .iter()
+                .map(|e| (e.old_string.as_str(), e.new_string.as_str()))
+                .collect(),
+        ),
+        ToolInput::Write(write) => (&write.file_path, vec![("", write.content.as_str())]),
+        _ => return None,
+    };
+
+    let mut diff = String::new();
+    let (mut lines_added, mut lines_removed) = (0, 0);
+    for (old, new) in pairs {
+        let text_diff = TextDiff::from_lines(old, new);
+        for change in text_diff.iter_all_changes() {
+            match change.tag() {
+                ChangeTag::Insert => lines_added += 1,
+                ChangeTag::Delete => lines_removed += 1,
+                ChangeTag::Equal => {}
+            }
+        }
+        diff.push_str(
+            &text_diff
+                .unified_diff()
+                .context_radius(CONTEXT_LINES)
+                .missing_newline_hint(false)
+                .to_string(),
+        );
+    }
+
+    let (diff, truncated) = cap(&diff);
+    Some(ToolDetail {
+        file_path: file_path.to_string(),
+        diff,
+        lines_added,
+        lines_removed,
+        truncated,
+    })
+}
+
+/// Keep at most `MAX_DIFF_LINES` lines and `MAX_DIFF_BYTES` bytes, cutting on char boundaries.
+fn cap(diff: &str) -> (String, bool) {
+    let mut out = String::new();
+    for (i, line) in diff.lines().enumerate() {
+        if i >= MAX_DIFF_LINES || out.len() + line.len() + 1 > MAX_DIFF_BYTES {
+            if out.is_empty() {
+                // A single huge line: keep its head.
+                let end = (0..=MAX_DIFF_BYTES.min(line.len()))
+                    .rev()
+                    .find(|&i| line.is_char_boundary(i))
+                    .unwrap_or(0);
+                out.push_str(&line[..end]);
+            }
+            return (out, true);
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    (out, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_edit_produces_unified_diff_with_counts() {
+        let input = ToolInput::parse(
+            "Edit",
+            &json!({
+                "file_path": "/src/lib.rs",
+                "old_string": "fn a() {\n    1\n}\n",
+                "new_string": "fn a() {\n    2\n    3\n}\n"
+            }),
+        );
+        let detail = tool_detail(&input).unwrap();
+        assert_eq!((detail.lines_added, detail.lines_removed), (2, 1));
+        assert!(detail.diff.starts_with("@@"));
+        assert!(detail.diff.contains("-    1\n+    2\n+    3\n"));
+        assert!(!detail.truncated);
+    }
+
+    #[test]
+    fn test_large_write_is_capped() {
+        let content: String = (0..500).map(|i| format!("line {i}\n")).collect();
+        let input = ToolInput::parse(
+            "Write",
+            &json!({"file_path": "/big.txt", "content": content}),
+        );
+        let detail = tool_detail(&input).unwrap();
+        assert_eq!(detail.lines_added, 500);
+        assert!(detail.truncated);
+        assert!(detail.diff.lines().count() <= MAX_DIFF_LINES);
+        assert!(tool_detail(&ToolInput::parse("Bash", &json!({"command": "ls"}))).is_none());
+    }
+}
@@ -1,7 +1,7 @@
 import { Container, Stage } from "@pixi/react";
 import { useEffect, useMemo, useRef } from "react";
 import { useShallow } from "zustand/shallow";
-import { startHudPruning, stopHudPruning, useAgentStore, useHudStore } from "../../store";
+import { selectActiveTodos, startHudPruning, stopHudPruning, useAgentStore, useHudStore, useLogStore, useTodoStore } from "../../store";
 import { DESK_CONFIGS } from "../../types";
 import type { AgentStatus } from "../../types";
 import {
@@ -35,6 +35,10 @@ export function OfficeCanvas(): JSX.Element {
       effects: state.effects,
     }))
   );
+  // Logs are newest-first
+  const latestToolDetail = useLogStore((state) => state.logs.find((log) => log.tool_detail)?.tool_detail ?? null);
+  const sessionId = useLogStore((state) => state.sessionId);
+  const architectTodos = useTodoStore((state) => selectActiveTodos(state, sessionId));
   const removeDocumentTransfer = useAgentStore((state) => state.removeDocumentTransfer);
   const clearExpiredTasks = useAgentStore((state) => state.clearExpiredTasks);
   const removeExpiredEffects = useAgentStore((state) => state.removeExpiredEffects);
@@ -111,6 +115,8 @@ export function OfficeCanvas(): JSX.Element {
                 hasError={Boolean(errorById[desk.id])}
                 agentStatus={agentStatus}
                 agentType={desk.agentType}
+                toolDetail={desk.agentType === "developer" ? latestToolDetail : null}
+                todos={desk.agentType === "architect" ? architectTodos : null}
               />
             );
           })}
@@ -57,6 +57,14 @@ export const VACATION_SIGN_HEIGHT = 18;
 export const DESK_VACATION_SIGN_X = 0;
 export const DESK_VACATION_SIGN_Y = 34;
 
+// Architect's TodoWrite whiteboard, to the right of the Architect desk (desk-relative)
+export const TODO_WHITEBOARD_X = 62;
+export const TODO_WHITEBOARD_Y = -42;
+export const TODO_WHITEBOARD_WIDTH = 104;
+export const TODO_WHITEBOARD_HEIGHT = 62;
+export const TODO_WHITEBOARD_MAX_ITEMS = 5;
+export const TODO_WHITEBOARD_MAX_CHARS = 18;
+
 // Document transfer animation
 export const DOCUMENT_TRANSFER_DURATION_MS = 600;
 export const DOCUMENT_SIZE = 16;
@@ -2,12 +2,13 @@ import { Container, Graphics, Text } from "@pixi/react";
 import { useCallback } from "react";
 import { TextStyle } from "pixi.js";
 import type { Graphics as PixiGraphics } from "pixi.js";
-import type { AgentStatus, AgentType } from "../../../../types";
-import { DESK_VACATION_SIGN_X, DESK_VACATION_SIGN_Y } from "../constants";
+import type { AgentStatus, AgentType, TodoItem, ToolDetail } from "../../../../types";
+import { DESK_VACATION_SIGN_X, DESK_VACATION_SIGN_Y, TODO_WHITEBOARD_X, TODO_WHITEBOARD_Y } from "../constants";
 import { MonitorScreen } from "./MonitorScreen";
 import { VacationSign } from "./VacationSign";
 import { AlertLight } from "./AlertLight";
 import { QueueIndicator } from "./QueueIndicator";
+import { TodoWhiteboard } from "./TodoWhiteboard";
 
 interface DeskProps {
   x: number;
@@ -17,6 +18,10 @@ interface DeskProps {
   hasError: boolean;
   agentStatus: AgentStatus;
   agentType: AgentType;
+  /** Latest file change, shown on the Developer's monitor */
+  toolDetail?: ToolDetail | null;
+  /** Current TodoWrite plan, shown on the Architect's whiteboard */
+  todos?: TodoItem[] | null;
 }
 
 const DESK_LABEL_STYLE = new TextStyle({
@@ -29,7 +34,7 @@ const DESK_LABEL_STYLE = new TextStyle({
   dropShadowDistance: 1,
 });
 
-export function Desk({ x, y, label, showVacation, hasError, agentStatus, agentType }: DeskProps): JSX.Element {
+export function Desk({ x, y, label, showVacation, hasError, agentStatus, agentType, toolDetail, todos }: DeskProps): JSX.Element {
   const draw = useCallback((g: PixiGraphics) => {
     g.clear();
     drawDeskBase(g);
@@ -41,7 +46,12 @@ export function Desk({ x, y, label, showVacation, hasError, agentStatus, agentTy
   return (
     <Container x={x} y={y}>
       <Graphics draw={draw} />
-      <MonitorScreen status={agentStatus} agentType={agentType} />
+      <MonitorScreen status={agentStatus} agentType={agentType} toolDetail={toolDetail} />
+      {todos && todos.length > 0 && (
+        <Container x={TODO_WHITEBOARD_X} y={TODO_WHITEBOARD_Y}>
+          <TodoWhiteboard todos={todos} />
+        </Container>
+      )}
       {hasError && (
         <Container x={25} y={-28}>
           <AlertLight />
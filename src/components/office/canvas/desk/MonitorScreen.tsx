@@ -2,7 +2,7 @@ import { Graphics } from "@pixi/react";
 import { useCallback } from "react";
 import type { Graphics as PixiGraphics } from "pixi.js";
 import { AGENT_COLORS } from "../../../../types";
-import type { AgentStatus, AgentType } from "../../../../types";
+import type { AgentStatus, AgentType, ToolDetail } from "../../../../types";
 import { useFrameAnimation } from "../hooks/useFrameAnimation";
 
 /** Background colors for each status */
@@ -17,9 +17,10 @@ const SCREEN_COLORS: Record<AgentStatus, number> = {
 interface MonitorScreenProps {
   status: AgentStatus;
   agentType: AgentType;
+  toolDetail?: ToolDetail | null;
 }
 
-export function MonitorScreen({ status, agentType }: MonitorScreenProps): JSX.Element {
+export function MonitorScreen({ status, agentType, toolDetail }: MonitorScreenProps): JSX.Element {
   const frame = useFrameAnimation(8, 150, status !== "idle");
 
   const draw = useCallback((g: PixiGraphics) => {
@@ -34,7 +35,11 @@ export function MonitorScreen({ status, agentType }: MonitorScreenProps): JSX.El
         drawIdleScreen(g);
         break;
       case "working":
-        drawWorkingScreen(g, agentType, frame);
+        if (toolDetail) {
+          drawDiffScreen(g, toolDetail, frame);
+        } else {
+          drawWorkingScreen(g, agentType, frame);
+        }
         break;
       case "thinking":
         drawThinkingScreen(g, frame);
@@ -46,7 +51,7 @@ export function MonitorScreen({ status, agentType }: MonitorScreenProps): JSX.El
         drawErrorScreen(g, frame);
         break;
     }
-  }, [status, agentType, frame]);
+  }, [status, agentType, toolDetail, frame]);
 
   return <Graphics draw={draw} />;
 }
@@ -82,6 +87,28 @@ function drawWorkingScreen(g: PixiGraphics, agentType: AgentType, frame: number)
   }
 }
 
+function drawDiffScreen(g: PixiGraphics, detail: ToolDetail, frame: number): void {
+  // Green/red bars proportional to lines added/removed
+  const total = Math.max(detail.lines_added + detail.lines_removed, 1);
+  const addedWidth = Math.round((28 * detail.lines_added) / total);
+
+  g.beginFill(0x40c060, 0.9);
+  g.drawRect(-14, -21, addedWidth, 3);
+  g.endFill();
+  g.beginFill(0xe05050, 0.9);
+  g.drawRect(-14 + addedWidth, -21, 28 - addedWidth, 3);
+  g.endFill();
+
+  // Diff lines, scrolling
+  for (let i = 0; i < 4; i++) {
+    const color = (i + frame) % 3 === 0 ? 0xe05050 : 0x40c060;
+    const width = 8 + ((i * 5 + frame) % 14);
+    g.beginFill(color, 0.6);
+    g.drawRect(-14, -16 + i * 3, width, 2);
+    g.endFill();
+  }
+}
+
 function drawThinkingScreen(g: PixiGraphics, frame: number): void {
   // Loading dots animation
   const dotCount = 3;
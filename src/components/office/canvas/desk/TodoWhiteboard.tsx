@@ -0,0 +1,110 @@
+import { Container, Graphics, Text } from "@pixi/react";
+import { useCallback, useMemo } from "react";
+import { TextStyle } from "pixi.js";
+import type { Graphics as PixiGraphics } from "pixi.js";
+import type { TodoItem, TodoStatus } from "../../../../types";
+import {
+  TODO_WHITEBOARD_HEIGHT,
+  TODO_WHITEBOARD_MAX_CHARS,
+  TODO_WHITEBOARD_MAX_ITEMS,
+  TODO_WHITEBOARD_WIDTH,
+} from "../constants";
+
+const TITLE_STYLE = new TextStyle({
+  fontFamily: '"Press Start 2P", monospace',
+  fontSize: 6,
+  fill: 0x1e3a8a,
+});
+
+const ITEM_STYLE = new TextStyle({
+  fontFamily: '"Press Start 2P", monospace',
+  fontSize: 5,
+  fill: 0x374151,
+});
+
+/** Checkbox color per status */
+const STATUS_COLORS: Record<TodoStatus, number> = {
+  pending: 0x9ca3af,
+  in_progress: 0xf59e0b,
+  completed: 0x22c55e,
+};
+
+const ROW_HEIGHT = 9;
+const TOP_PADDING = 14;
+
+interface TodoWhiteboardProps {
+  todos: TodoItem[];
+}
+
+/** The Architect's whiteboard: the current TodoWrite plan, in-progress items first */
+export function TodoWhiteboard({ todos }: TodoWhiteboardProps): JSX.Element {
+  const done = todos.filter((todo) => todo.status === "completed").length;
+  const visible = useMemo(
+    () => [...todos].sort((a, b) => statusRank(a.status) - statusRank(b.status)).slice(0, TODO_WHITEBOARD_MAX_ITEMS),
+    [todos]
+  );
+
+  const draw = useCallback((g: PixiGraphics) => {
+    g.clear();
+
+    // Shadow
+    g.beginFill(0x000000, 0.2);
+    g.drawRect(2, 2, TODO_WHITEBOARD_WIDTH, TODO_WHITEBOARD_HEIGHT);
+    g.endFill();
+
+    // Frame and board
+    g.beginFill(0x9ca3af);
+    g.drawRect(0, 0, TODO_WHITEBOARD_WIDTH, TODO_WHITEBOARD_HEIGHT);
+    g.endFill();
+    g.beginFill(0xf8fafc);
+    g.drawRect(2, 2, TODO_WHITEBOARD_WIDTH - 4, TODO_WHITEBOARD_HEIGHT - 4);
+    g.endFill();
+
+    // Marker tray
+    g.beginFill(0x6b7280);
+    g.drawRect(10, TODO_WHITEBOARD_HEIGHT, TODO_WHITEBOARD_WIDTH - 20, 3);
+    g.endFill();
+
+    // Progress bar under the title
+    const barWidth = TODO_WHITEBOARD_WIDTH - 12;
+    g.beginFill(0xe5e7eb);
+    g.drawRect(6, 10, barWidth, 2);
+    g.endFill();
+    if (todos.length > 0) {
+      g.beginFill(0x22c55e);
+      g.drawRect(6, 10, Math.round((barWidth * done) / todos.length), 2);
+      g.endFill();
+    }
+
+    visible.forEach((todo, i) => {
+      g.beginFill(STATUS_COLORS[todo.status]);
+      g.drawRect(6, TOP_PADDING + i * ROW_HEIGHT + 1, 4, 4);
+      g.endFill();
+    });
+  }, [todos, visible, done]);
+
+  return (
+    <Container>
+      <Graphics draw={draw} />
+      <Text text={`PLAN ${done}/${todos.length}`} style={TITLE_STYLE} x={6} y={3} />
+      {visible.map((todo, i) => (
+        <Text
+          key={`${i}-${todo.content}`}
+          text={truncate(todo.status === "in_progress" ? todo.activeForm || todo.content : todo.content)}
+          style={ITEM_STYLE}
+          x={13}
+          y={TOP_PADDING + i * ROW_HEIGHT}
+          alpha={todo.status === "completed" ? 0.5 : 1}
+        />
+      ))}
+    </Container>
+  );
+}
+
+function statusRank(status: TodoStatus): number {
+  return status === "in_progress" ? 0 : status === "pending" ? 1 : 2;
+}
+
+function truncate(text: string): string {
+  return text.length > TODO_WHITEBOARD_MAX_CHARS ? `${text.slice(0, TODO_WHITEBOARD_MAX_CHARS - 1)}…` : text;
+}
@@ -6,3 +6,4 @@ export { MonitorScreen } from "./MonitorScreen";
 export { VacationSign } from "./VacationSign";
 export { AlertLight } from "./AlertLight";
 export { QueueIndicator } from "./QueueIndicator";
+export { TodoWhiteboard } from "./TodoWhiteboard";
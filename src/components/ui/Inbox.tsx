@@ -1,5 +1,6 @@
+import { useState } from "react";
 import { useLogStore, useSettingsStore } from "../../store";
-import type { LogEntry, LogEntryType } from "../../types";
+import type { LogEntry, LogEntryType, ToolDetail } from "../../types";
 
 const TYPE_COLORS: Record<LogEntryType, string> = {
   tool_call: "text-blue-400",
@@ -21,7 +22,30 @@ const TYPE_ICONS: Record<LogEntryType, string> = {
   session_end: "<<",
 };
 
+function diffLineClass(line: string): string {
+  if (line.startsWith("@@")) return "text-purple-400";
+  if (line.startsWith("+")) return "text-green-400";
+  if (line.startsWith("-")) return "text-red-400";
+  return "text-gray-500";
+}
+
+function DiffPreview({ detail }: { detail: ToolDetail }) {
+  return (
+    <pre className="mt-1 max-h-48 overflow-auto bg-black/30 rounded p-1.5 text-[10px] leading-tight font-mono">
+      {detail.diff.split("\n").map((line, i) => (
+        <div key={i} className={diffLineClass(line)}>
+          {line || " "}
+        </div>
+      ))}
+      {detail.truncated && <div className="text-gray-500">… diff truncated</div>}
+    </pre>
+  );
+}
+
 function LogEntryItem({ entry }: { entry: LogEntry }) {
+  const [showDiff, setShowDiff] = useState(false);
+  const detail = entry.tool_detail;
+
   const colorClass = TYPE_COLORS[entry.entry_type] || "text-gray-300";
   const icon = TYPE_ICONS[entry.entry_type] || "?";
 
@@ -34,13 +58,24 @@ function LogEntryItem({ entry }: { entry: LogEntry }) {
             {entry.tool_name}
           </span>
         )}
+        {detail && (
+          <button
+            onClick={() => setShowDiff((v) => !v)}
+            className="font-mono hover:underline"
+            title={detail.file_path}
+          >
+            <span className="text-green-400">+{detail.lines_added}</span>{" "}
+            <span className="text-red-400">-{detail.lines_removed}</span>
+          </button>
+        )}
         {entry.timestamp && (
           <span className="text-gray-500 ml-auto">
-            {entry.timestamp.slice(11, 19)}
+            {new Date(entry.timestamp).toLocaleTimeString([], { hour12: false })}
           </span>
         )}
       </div>
       <p className="mt-1 text-xs text-gray-400 truncate">{entry.content}</p>
+      {detail && showDiff && <DiffPreview detail={detail} />}
     </div>
   );
 }
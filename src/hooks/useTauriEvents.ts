@@ -1,7 +1,8 @@
 import { useEffect, useRef } from "react";
 import { listen } from "@tauri-apps/api/event";
-import { useAgentStore, useLogStore, useHudStore, type BatchUpdateData, type EffectKind } from "../store";
-import type { Agent, AppEvent, LogEntry } from "../types";
+import { useAgentStore, useLogStore, useHudStore, useTodoStore, type EffectKind } from "../store";
+import type { AgentState, AppEvent, LogEntry, OfficeSnapshot, SequencedEvent } from "../types";
+import { getEventsSince, getOfficeSnapshot, getTodos } from "../services";
 import { TOOL_COLORS } from "../types";
 import {
   getEffectForTool,
@@ -15,47 +16,67 @@ function isTauriEnv(): boolean {
   return typeof window !== "undefined" && "__TAURI__" in window;
 }
 
+const BROWSER_RECONNECT_MS = 2000;
+
+/**
+ * WebSocket URL of the backend's browser server.
+ * Same origin when the page is served by the backend; override with VITE_OFFICE_WS_URL for `npm run dev`.
+ */
+function getBrowserEventsUrl(): string {
+  const override = import.meta.env.VITE_OFFICE_WS_URL as string | undefined;
+  if (override) return override;
+  const protocol = window.location.protocol === "https:" ? "wss" : "ws";
+  return `${protocol}://${window.location.host}/ws`;
+}
+
+/** Subscribe to AppEvents over WebSocket, reconnecting until the returned cleanup is called */
+function connectBrowserEvents(onEvent: (event: AppEvent) => void): () => void {
+  let socket: WebSocket | null = null;
+  let reconnectTimer: ReturnType<typeof setTimeout> | null = null;
+  let closed = false;
+
+  const connect = () => {
+    socket = new WebSocket(getBrowserEventsUrl());
+    socket.onmessage = (message) => {
+      try {
+        onEvent(JSON.parse(message.data) as AppEvent);
+      } catch (err) {
+        console.warn("[useTauriEvents] Ignoring malformed event", err);
+      }
+    };
+    socket.onclose = () => {
+      if (!closed) reconnectTimer = setTimeout(connect, BROWSER_RECONNECT_MS);
+    };
+  };
+
+  connect();
+
+  return () => {
+    closed = true;
+    if (reconnectTimer) clearTimeout(reconnectTimer);
+    socket?.close();
+  };
+}
+
 export function useTauriEvents(): void {
-  const {
-    updateAgent,
-    processBatchUpdate,
-    setAgentVacation,
-    setAgentError,
-    startDocumentTransfer,
-    setLastActiveAgent,
-    recordToolCall: agentRecordToolCall,
-    recordError: agentRecordError,
-    enqueueEffect,
-  } = useAgentStore();
+  const { updateAgent, updateAgentsBatch, startDocumentTransfer, enqueueEffect } = useAgentStore();
   const { addLog, addLogsBatch, setSessionId, setWatcherStatus } = useLogStore();
   const { recordToolCall, recordToolResult, recordError, recordAgentSwitch, recordEventsBatch, setRateLimitActive } = useHudStore();
+  const { setSessionTodos } = useTodoStore();
   const lastActiveAgentIdRef = useRef<string | null>(null);
 
   useEffect(() => {
-    if (!isTauriEnv()) {
-      console.log("[useTauriEvents] Not in Tauri environment, skipping event listener");
-      return;
-    }
-
-    const unlisten = listen<AppEvent>("app-event", (event) => {
-      const appEvent = event.payload;
-
+    const handleAppEvent = (appEvent: AppEvent) => {
       switch (appEvent.type) {
         case "LogEntry":
           handleLogEntry(appEvent.payload, {
             addLog,
-            setAgentVacation,
-            setAgentError,
-            startDocumentTransfer,
-            setLastActiveAgent,
             lastActiveAgentIdRef,
             recordToolCall,
             recordToolResult,
             recordError,
             recordAgentSwitch,
             setRateLimitActive,
-            agentRecordToolCall,
-            agentRecordError,
             enqueueEffect,
           });
           break;
@@ -79,20 +100,106 @@ export function useTauriEvents(): void {
         case "BatchUpdate":
           handleBatchUpdate(appEvent.payload.logs, appEvent.payload.agents, {
             addLogsBatch,
-            processBatchUpdate,
+            updateAgentsBatch,
             lastActiveAgentIdRef,
             recordEventsBatch,
             setRateLimitActive,
             enqueueEffect,
           });
           break;
+
+        case "Handoff":
+          startDocumentTransfer(appEvent.payload.from, appEvent.payload.to, appEvent.payload.tool_name);
+          break;
+
+        case "TodoListUpdate":
+          setSessionTodos(appEvent.payload.session_id, appEvent.payload.todos);
+          break;
       }
-    });
+    };
+
+    if (!isTauriEnv()) {
+      // Browser mode: stream the same events from the backend's embedded server.
+      return connectBrowserEvents(handleAppEvent);
+    }
+
+    const sequencer = createSequencer(handleAppEvent);
+    const unlisten = listen<SequencedEvent>("app-event", (event) => sequencer.receive(event.payload));
+    // Catch up after a webview reload instead of waiting for the next file change.
+    sequencer.start();
 
     return () => {
       unlisten.then((fn) => fn());
     };
-  }, [updateAgent, processBatchUpdate, addLog, addLogsBatch, setSessionId, setWatcherStatus, setAgentVacation, setAgentError, startDocumentTransfer, setLastActiveAgent, recordToolCall, recordToolResult, recordError, recordAgentSwitch, recordEventsBatch, setRateLimitActive, agentRecordToolCall, agentRecordError, enqueueEffect]);
+  }, [updateAgent, updateAgentsBatch, addLog, addLogsBatch, setSessionId, setWatcherStatus, startDocumentTransfer, recordToolCall, recordToolResult, recordError, recordAgentSwitch, recordEventsBatch, setRateLimitActive, enqueueEffect, setSessionTodos]);
+}
+
+/**
+ * Apply events in `seq` order. Events already covered by the snapshot are dropped, and a
+ * jump in `seq` is filled from the backend's event buffer (or a fresh snapshot if that is gone).
+ */
+function createSequencer(apply: (event: AppEvent) => void) {
+  let lastSeq = 0;
+  let resyncing = false;
+  const pending: SequencedEvent[] = [];
+
+  const deliver = (event: SequencedEvent) => {
+    if (event.seq <= lastSeq) return;
+    lastSeq = event.seq;
+    apply(event);
+  };
+
+  const loadSnapshot = async () => {
+    const [snapshot, todos] = await Promise.all([getOfficeSnapshot(), getTodos()]);
+    restoreSnapshot(snapshot);
+    useTodoStore.getState().loadTodos(todos);
+    lastSeq = Math.max(lastSeq, snapshot.seq);
+  };
+
+  const catchUp = async (load: () => Promise<void>) => {
+    resyncing = true;
+    try {
+      await load();
+    } catch (err) {
+      // Stop gap checks until the next event so a broken backend call cannot loop.
+      console.warn("[useTauriEvents] Resync failed", err);
+      lastSeq = 0;
+    } finally {
+      resyncing = false;
+    }
+    for (const event of pending.splice(0).sort((a, b) => a.seq - b.seq)) receive(event);
+  };
+
+  const receive = (event: SequencedEvent) => {
+    if (resyncing) {
+      pending.push(event);
+      return;
+    }
+    if (lastSeq > 0 && event.seq > lastSeq + 1) {
+      pending.push(event);
+      void catchUp(async () => {
+        try {
+          for (const missed of await getEventsSince(lastSeq)) deliver(missed);
+        } catch {
+          await loadSnapshot();
+        }
+      });
+      return;
+    }
+    deliver(event);
+  };
+
+  return { receive, start: () => void catchUp(loadSnapshot) };
+}
+
+/** Seed the stores from backend state; logs are only restored into an empty Inbox. */
+function restoreSnapshot(snapshot: OfficeSnapshot): void {
+  useAgentStore.getState().restoreAgents(snapshot.agents);
+  const logStore = useLogStore.getState();
+  if (logStore.logs.length === 0) logStore.addLogsBatch(snapshot.logs);
+  if (snapshot.sessions.length > 0) logStore.setSessionId(snapshot.sessions[0]);
+  if (snapshot.watcher) logStore.setWatcherStatus(snapshot.watcher.active, snapshot.watcher.path);
+  useHudStore.getState().restoreCounters(snapshot.hud);
 }
 
 // =============================================================================
@@ -101,36 +208,28 @@ export function useTauriEvents(): void {
 
 interface LogEntryHandlers {
   addLog: (entry: LogEntry) => void;
-  setAgentVacation: (id: string, on: boolean) => void;
-  setAgentError: (id: string, hasError: boolean) => void;
-  startDocumentTransfer: (fromAgentId: string, toAgentId: string, toolName?: string | null) => void;
-  setLastActiveAgent: (id: string) => void;
   lastActiveAgentIdRef: { current: string | null };
   recordToolCall: () => void;
   recordToolResult: () => void;
   recordError: () => void;
   recordAgentSwitch: () => void;
   setRateLimitActive: (active: boolean) => void;
-  agentRecordToolCall: (id: string) => void;
-  agentRecordError: (id: string) => void;
   enqueueEffect: (agentId: string, kind: EffectKind, color: number, durationMs?: number) => void;
 }
 
+/**
+ * Log, HUD counters and visual effects for one entry. Agent state (vacation, errors,
+ * last activity) arrives separately as backend `AgentUpdate`s.
+ */
 function handleLogEntry(entry: LogEntry, handlers: LogEntryHandlers): void {
   const {
     addLog,
-    setAgentVacation,
-    setAgentError,
-    startDocumentTransfer,
-    setLastActiveAgent,
     lastActiveAgentIdRef,
     recordToolCall,
     recordToolResult,
     recordError,
     recordAgentSwitch,
     setRateLimitActive,
-    agentRecordToolCall,
-    agentRecordError,
     enqueueEffect,
   } = handlers;
 
@@ -138,52 +237,36 @@ function handleLogEntry(entry: LogEntry, handlers: LogEntryHandlers): void {
 
   const inferredAgentId = inferAgentId(entry);
   const agentId = inferredAgentId ?? lastActiveAgentIdRef.current;
-  const isActivity = isToolActivity(entry.entry_type);
 
   // HUD: Record tool events + Visual effects
   if (entry.entry_type === "tool_call") {
     recordToolCall();
     if (agentId) {
-      agentRecordToolCall(agentId);
       const effect = getEffectForTool(entry.tool_name);
       enqueueEffect(agentId, effect.kind, effect.color);
     }
   }
   if (entry.entry_type === "tool_result") recordToolResult();
 
-  // Rate limit detection and vacation state
+  // HUD rate limit banner
   if (isLimitReachedMessage(entry.content)) {
     setRateLimitActive(true);
-    if (agentId) setAgentVacation(agentId, true);
-  } else if (isActivity) {
+  } else if (isToolActivity(entry.entry_type)) {
     setRateLimitActive(false);
-    if (agentId) setAgentVacation(agentId, false);
   }
 
-  // Error state tracking + Visual effects
   if (entry.entry_type === "error") {
     recordError();
-    if (agentId) {
-      setAgentError(agentId, true);
-      agentRecordError(agentId);
-      enqueueEffect(agentId, "errorBurst", TOOL_COLORS.error, 1000);
-    }
-  } else if (agentId && isActivity) {
-    setAgentError(agentId, false);
+    if (agentId) enqueueEffect(agentId, "errorBurst", TOOL_COLORS.error, 1000);
   }
 
-  // Document transfer tracking (only on tool_call with valid agent)
+  // HUD agent switches; the ref only targets effects for entries without a tool name.
   if (entry.entry_type === "tool_call" && inferredAgentId) {
-    const previousAgentId = lastActiveAgentIdRef.current;
-    if (previousAgentId && previousAgentId !== inferredAgentId) {
-      startDocumentTransfer(previousAgentId, inferredAgentId, entry.tool_name);
+    if (lastActiveAgentIdRef.current && lastActiveAgentIdRef.current !== inferredAgentId) {
       recordAgentSwitch();
     }
-    lastActiveAgentIdRef.current = inferredAgentId;
-    setLastActiveAgent(inferredAgentId);
-  } else if (inferredAgentId) {
-    lastActiveAgentIdRef.current = inferredAgentId;
   }
+  if (inferredAgentId) lastActiveAgentIdRef.current = inferredAgentId;
 }
 
 function inferAgentId(entry: LogEntry): string | null {
@@ -199,72 +282,48 @@ function inferAgentId(entry: LogEntry): string | null {
 
 interface BatchUpdateHandlers {
   addLogsBatch: (entries: LogEntry[]) => void;
-  processBatchUpdate: (data: BatchUpdateData) => void;
+  updateAgentsBatch: (states: AgentState[]) => void;
   lastActiveAgentIdRef: { current: string | null };
   recordEventsBatch: (entries: LogEntry[], agentSwitchCount: number) => void;
   setRateLimitActive: (active: boolean) => void;
   enqueueEffect: (agentId: string, kind: EffectKind, color: number, durationMs?: number) => void;
 }
 
-function handleBatchUpdate(logs: LogEntry[], agents: Agent[], handlers: BatchUpdateHandlers): void {
-  const { addLogsBatch, processBatchUpdate, lastActiveAgentIdRef, recordEventsBatch, setRateLimitActive, enqueueEffect } = handlers;
+function handleBatchUpdate(logs: LogEntry[], agents: AgentState[], handlers: BatchUpdateHandlers): void {
+  const { addLogsBatch, updateAgentsBatch, lastActiveAgentIdRef, recordEventsBatch, setRateLimitActive, enqueueEffect } = handlers;
 
   addLogsBatch(logs);
+  updateAgentsBatch(agents);
 
-  const vacations: Record<string, boolean> = {};
-  const errors: Record<string, boolean> = {};
-  const newDocumentTransfers: BatchUpdateData["newDocumentTransfers"] = [];
-  let lastActiveId: string | null = null;
   let agentSwitchCount = 0;
   let rateLimitDetected = false;
   let activityResumed = false;
 
-  const moodToolCalls: string[] = [];
-  const moodErrors: string[] = [];
-
   for (const entry of logs) {
     const inferredAgentId = inferAgentId(entry);
     const agentId = inferredAgentId ?? lastActiveAgentIdRef.current;
-    const isActivity = isToolActivity(entry.entry_type);
 
     if (isLimitReachedMessage(entry.content)) {
       rateLimitDetected = true;
-      if (agentId) vacations[agentId] = true;
-    } else if (isActivity) {
+    } else if (isToolActivity(entry.entry_type)) {
       activityResumed = true;
-      if (agentId) vacations[agentId] = false;
     }
 
     if (entry.entry_type === "error" && agentId) {
-      errors[agentId] = true;
-      moodErrors.push(agentId);
       enqueueEffect(agentId, "errorBurst", TOOL_COLORS.error, 1000);
-    } else if (agentId && isActivity) {
-      errors[agentId] = false;
     }
 
     if (entry.entry_type === "tool_call" && inferredAgentId) {
-      moodToolCalls.push(inferredAgentId);
       const effect = getEffectForTool(entry.tool_name);
       if (effect) enqueueEffect(inferredAgentId, effect.kind, effect.color);
       const previousAgentId = lastActiveAgentIdRef.current;
       if (previousAgentId && previousAgentId !== inferredAgentId) {
-        newDocumentTransfers.push({ from: previousAgentId, to: inferredAgentId, toolName: entry.tool_name });
         agentSwitchCount++;
       }
-      lastActiveAgentIdRef.current = inferredAgentId;
-      lastActiveId = inferredAgentId;
-    } else if (inferredAgentId) {
-      lastActiveAgentIdRef.current = inferredAgentId;
     }
+    if (inferredAgentId) lastActiveAgentIdRef.current = inferredAgentId;
   }
 
-  const moodEvents = (moodToolCalls.length > 0 || moodErrors.length > 0)
-    ? { toolCalls: moodToolCalls, errors: moodErrors }
-    : undefined;
-
-  processBatchUpdate({ agentList: agents, vacations, errors, newDocumentTransfers, lastActiveId, moodEvents });
-
   if (rateLimitDetected) {
     setRateLimitActive(true);
   } else if (activityResumed) {
@@ -1,5 +1,18 @@
-import { invoke } from "@tauri-apps/api/core";
-import type { Agent } from "../types";
+import { Channel, invoke } from "@tauri-apps/api/core";
+import type {
+  Agent,
+  AppSettings,
+  ExportQuery,
+  FileActivity,
+  FilterSettings,
+  OfficeSnapshot,
+  ProjectInfo,
+  SearchPage,
+  SearchQuery,
+  SequencedEvent,
+  SubscriptionFilter,
+  SessionTodos,
+} from "../types";
 
 export async function getClaudeHome(): Promise<string> {
   return invoke<string>("get_claude_home");
@@ -9,6 +22,91 @@ export async function getAgents(): Promise<Agent[]> {
   return invoke<Agent[]>("get_agents");
 }
 
+/** Current office state held by the backend, for restoring after a webview reload. */
+export async function getOfficeSnapshot(): Promise<OfficeSnapshot> {
+  return invoke<OfficeSnapshot>("get_office_snapshot");
+}
+
+/** Buffered events after `seq`; rejects when they were already dropped (resync from a snapshot). */
+export async function getEventsSince(seq: number): Promise<SequencedEvent[]> {
+  return invoke<SequencedEvent[]>("get_events_since", { seq });
+}
+
+/**
+ * Stream only the events matching `filter` (e.g. a detached inbox or a per-session office);
+ * omitted lists match everything.
+ * Resolves to a function that closes the stream. `seq` stays global, so gaps are expected.
+ */
+export async function subscribeEvents(
+  filter: Partial<SubscriptionFilter>,
+  onEvent: (event: SequencedEvent) => void
+): Promise<() => Promise<boolean>> {
+  const channel = new Channel<SequencedEvent>();
+  channel.onmessage = onEvent;
+  const id = await invoke<number>("subscribe", { filter, onEvent: channel });
+  return () => invoke<boolean>("unsubscribe", { id });
+}
+
+export async function getSettings(): Promise<AppSettings> {
+  return invoke<AppSettings>("get_settings");
+}
+
+export async function saveSettings(settings: AppSettings): Promise<void> {
+  return invoke<void>("save_settings", { settings });
+}
+
+/** Write a session's HTML report; returns the file path written. */
+export async function exportSessionReport(
+  sessionId: string,
+  outputPath?: string
+): Promise<string> {
+  return invoke<string>("export_session_report", {
+    sessionId,
+    outputPath: outputPath ?? null,
+  });
+}
+
+/** Export persisted entries; returns the number of records written. */
+export async function exportEntries(
+  query: ExportQuery,
+  outputPath: string
+): Promise<number> {
+  return invoke<number>("export_entries", { query, outputPath });
+}
+
+/** Full-text search over persisted history, newest first. */
+export async function searchLogs(query: SearchQuery): Promise<SearchPage> {
+  return invoke<SearchPage>("search_logs", { query });
+}
+
+/** Per-file read/edit/write counts, most-touched first. */
+export async function getFileActivity(
+  sessionId?: string
+): Promise<FileActivity[]> {
+  return invoke<FileActivity[]>("get_file_activity", {
+    sessionId: sessionId ?? null,
+  });
+}
+
+/** Current TodoWrite plan per session. */
+export async function getTodos(sessionId?: string): Promise<SessionTodos[]> {
+  return invoke<SessionTodos[]>("get_todos", { sessionId: sessionId ?? null });
+}
+
+/** Projects under `~/.claude/projects` with their sessions, most recently active first. */
+export async function listProjects(): Promise<ProjectInfo[]> {
+  return invoke<ProjectInfo[]>("list_projects");
+}
+
+export async function getFilters(): Promise<FilterSettings> {
+  return invoke<FilterSettings>("get_filters");
+}
+
+/** Replace the watcher's include/exclude rules (validated and persisted by the backend). */
+export async function saveFilters(filters: FilterSettings): Promise<void> {
+  return invoke<void>("save_filters", { filters });
+}
+
 export async function startWatching(): Promise<boolean> {
   return invoke<boolean>("start_watching");
 }
@@ -1,5 +1,5 @@
 import { create } from "zustand";
-import type { Agent, AgentStatus } from "../types";
+import type { Agent, AgentState as BackendAgentState, AgentStatus } from "../types";
 
 export interface DocumentTransfer {
   id: string;
@@ -32,50 +32,75 @@ let effectSeq = 0;
 // Track last effect time per agent+kind for debouncing
 const lastEffectTimeByKey: Record<string, number> = {};
 
-export interface BatchUpdateData {
-  agentList: Agent[];
-  vacations: Record<string, boolean>;
-  errors: Record<string, boolean>;
-  newDocumentTransfers: Array<{ from: string; to: string; toolName?: string | null }>;
-  lastActiveId: string | null;
-  moodEvents?: { toolCalls: string[]; errors: string[] };
-}
-
 interface AgentState {
   agents: Record<string, Agent>;
+  // Mood inputs, copied from the backend `AgentState`; nothing here is inferred from log entries.
   vacationById: Record<string, boolean>;
   errorById: Record<string, boolean>;
+  lastToolCallAtById: Record<string, number>;
+  lastErrorAtById: Record<string, number>;
   documentTransfers: DocumentTransfer[];
   lastActiveAgentId: string | null;
   lastTaskUpdateById: Record<string, number>; // timestamp when task was last updated
-  // Mood tracking
-  lastToolCallAtById: Record<string, number>;
-  lastErrorAtById: Record<string, number>;
   initializeAgents: () => void;
-  updateAgent: (agent: Agent) => void;
-  updateAgentsBatch: (agentList: Agent[]) => void;
-  processBatchUpdate: (data: BatchUpdateData) => void;
+  updateAgent: (state: BackendAgentState) => void;
+  updateAgentsBatch: (states: BackendAgentState[]) => void;
   setAgentStatus: (id: string, status: AgentStatus) => void;
   setAgentTask: (id: string, task: string | null) => void;
-  setAgentVacation: (id: string, on: boolean) => void;
-  setAgentVacationsBatch: (vacations: Record<string, boolean>) => void;
-  setAgentError: (id: string, hasError: boolean) => void;
-  setAgentErrorsBatch: (errors: Record<string, boolean>) => void;
   startDocumentTransfer: (fromAgentId: string, toAgentId: string, toolName?: string | null) => void;
   removeDocumentTransfer: (id: string) => void;
   clearDocumentTransfers: () => void;
-  setLastActiveAgent: (id: string) => void;
   resetAllToIdle: () => void;
   clearExpiredTasks: (timeoutMs: number) => void;
-  // Mood tracking
-  recordToolCall: (id: string) => void;
-  recordError: (id: string) => void;
+  restoreAgents: (states: BackendAgentState[]) => void;
   // Visual effects
   effects: VisualEffect[];
   enqueueEffect: (agentId: string, kind: EffectKind, color: number, durationMs?: number) => void;
   removeExpiredEffects: (now: number) => void;
 }
 
+type AgentStateUpdate = Pick<
+  AgentState,
+  "agents" | "vacationById" | "errorById" | "lastToolCallAtById" | "lastErrorAtById" | "lastActiveAgentId" | "lastTaskUpdateById"
+>;
+
+/**
+ * Merge backend agent states into the store. Unless `includeIdle` is set, an idle agent
+ * that has not been seen yet stays out of the office until it starts working.
+ */
+function mergeAgentStates(state: AgentState, states: BackendAgentState[], includeIdle: boolean): AgentStateUpdate {
+  const now = Date.now();
+  const agents = { ...state.agents };
+  const vacationById = { ...state.vacationById };
+  const errorById = { ...state.errorById };
+  const lastToolCallAtById = { ...state.lastToolCallAtById };
+  const lastErrorAtById = { ...state.lastErrorAtById };
+  const lastTaskUpdateById = { ...state.lastTaskUpdateById };
+  let lastActiveAgentId = state.lastActiveAgentId;
+  let lastActiveAt = -Infinity;
+
+  for (const { agent, rate_limited, last_tool_call, last_error, last_activity } of states) {
+    if (!includeIdle && !agents[agent.id] && agent.status === "idle") continue;
+
+    const taskChanged = agents[agent.id]?.current_task !== agent.current_task;
+    agents[agent.id] = agent;
+    if (taskChanged && agent.current_task) lastTaskUpdateById[agent.id] = now;
+
+    vacationById[agent.id] = rate_limited;
+    errorById[agent.id] = agent.status === "error";
+    if (last_tool_call) lastToolCallAtById[agent.id] = Date.parse(last_tool_call);
+    if (last_error) lastErrorAtById[agent.id] = Date.parse(last_error);
+
+    const activeAt = last_activity ? Date.parse(last_activity) : NaN;
+    if (agent.status !== "idle" && activeAt > lastActiveAt) {
+      lastActiveAt = activeAt;
+      lastActiveAgentId = agent.id;
+    }
+  }
+
+  return { agents, vacationById, errorById, lastToolCallAtById, lastErrorAtById, lastActiveAgentId, lastTaskUpdateById };
+}
+
 export const useAgentStore = create<AgentState>((set) => ({
   agents: {},
   vacationById: {},
@@ -93,118 +118,13 @@ export const useAgentStore = create<AgentState>((set) => ({
     set({ agents: {}, vacationById: {}, errorById: {}, documentTransfers: [], lastActiveAgentId: null, lastTaskUpdateById: {}, lastToolCallAtById: {}, lastErrorAtById: {} });
   },
 
-  updateAgent: (agent) => {
-    set((state) => {
-      // If we haven't seen this agent before and it's idle, ignore it.
-      // This prevents agents from appearing in the office until they start working.
-      if (!state.agents[agent.id] && agent.status === "idle") {
-        return state;
-      }
-
-      const now = Date.now();
-      const taskChanged = state.agents[agent.id]?.current_task !== agent.current_task;
-
-      return {
-        agents: {
-          ...state.agents,
-          [agent.id]: agent,
-        },
-        lastTaskUpdateById: taskChanged && agent.current_task
-          ? { ...state.lastTaskUpdateById, [agent.id]: now }
-          : state.lastTaskUpdateById,
-      };
-    });
-  },
-
-  updateAgentsBatch: (agentList) => {
-    if (agentList.length === 0) return;
-    set((state) => {
-      const now = Date.now();
-      const newAgents = { ...state.agents };
-      const newLastTaskUpdate = { ...state.lastTaskUpdateById };
-
-      for (const agent of agentList) {
-        // If we haven't seen this agent before and it's idle, skip it
-        if (!newAgents[agent.id] && agent.status === "idle") {
-          continue;
-        }
-
-        const taskChanged = newAgents[agent.id]?.current_task !== agent.current_task;
-        newAgents[agent.id] = agent;
-
-        if (taskChanged && agent.current_task) {
-          newLastTaskUpdate[agent.id] = now;
-        }
-      }
-
-      return {
-        agents: newAgents,
-        lastTaskUpdateById: newLastTaskUpdate,
-      };
-    });
+  updateAgent: (agentState) => {
+    set((state) => mergeAgentStates(state, [agentState], false));
   },
 
-  processBatchUpdate: ({ agentList, vacations, errors, newDocumentTransfers, lastActiveId, moodEvents }) => {
-    set((state) => {
-      const now = Date.now();
-      const startedAt = performance.now();
-      const newAgents = { ...state.agents };
-      const newLastTaskUpdate = { ...state.lastTaskUpdateById };
-
-      for (const agent of agentList) {
-        if (!newAgents[agent.id] && agent.status === "idle") {
-          continue;
-        }
-        const taskChanged = newAgents[agent.id]?.current_task !== agent.current_task;
-        newAgents[agent.id] = agent;
-        if (taskChanged && agent.current_task) {
-          newLastTaskUpdate[agent.id] = now;
-        }
-      }
-
-      const newTransfers = newDocumentTransfers.map((t) => ({
-        id: `${Date.now()}-${documentTransferSeq++}`,
-        fromAgentId: t.from,
-        toAgentId: t.to,
-        startedAt,
-        toolName: t.toolName ?? null,
-      }));
-
-      const hasVacationUpdates = Object.keys(vacations).length > 0;
-      const hasErrorUpdates = Object.keys(errors).length > 0;
-      const hasNewTransfers = newTransfers.length > 0;
-
-      // Mood events processing
-      let newToolCallAt = state.lastToolCallAtById;
-      let newErrorAt = state.lastErrorAtById;
-      if (moodEvents) {
-        if (moodEvents.toolCalls.length > 0) {
-          newToolCallAt = { ...state.lastToolCallAtById };
-          for (const id of moodEvents.toolCalls) {
-            newToolCallAt[id] = now;
-          }
-        }
-        if (moodEvents.errors.length > 0) {
-          newErrorAt = { ...state.lastErrorAtById };
-          for (const id of moodEvents.errors) {
-            newErrorAt[id] = now;
-          }
-        }
-      }
-
-      return {
-        agents: newAgents,
-        lastTaskUpdateById: newLastTaskUpdate,
-        vacationById: hasVacationUpdates ? { ...state.vacationById, ...vacations } : state.vacationById,
-        errorById: hasErrorUpdates ? { ...state.errorById, ...errors } : state.errorById,
-        documentTransfers: hasNewTransfers
-          ? [...state.documentTransfers, ...newTransfers].slice(-MAX_DOCUMENT_TRANSFERS)
-          : state.documentTransfers,
-        lastActiveAgentId: lastActiveId ?? state.lastActiveAgentId,
-        lastToolCallAtById: newToolCallAt,
-        lastErrorAtById: newErrorAt,
-      };
-    });
+  updateAgentsBatch: (states) => {
+    if (states.length === 0) return;
+    set((state) => mergeAgentStates(state, states, false));
   },
 
   setAgentStatus: (id, status) => {
@@ -237,36 +157,6 @@ export const useAgentStore = create<AgentState>((set) => ({
     });
   },
 
-  setAgentVacation: (id, on) => {
-    set((state) => ({
-      vacationById: {
-        ...state.vacationById,
-        [id]: on,
-      },
-    }));
-  },
-
-  setAgentVacationsBatch: (vacations) => {
-    const entries = Object.entries(vacations);
-    if (entries.length === 0) return;
-    set((state) => ({ vacationById: { ...state.vacationById, ...vacations } }));
-  },
-
-  setAgentError: (id, hasError) => {
-    set((state) => ({
-      errorById: {
-        ...state.errorById,
-        [id]: hasError,
-      },
-    }));
-  },
-
-  setAgentErrorsBatch: (errors) => {
-    const entries = Object.entries(errors);
-    if (entries.length === 0) return;
-    set((state) => ({ errorById: { ...state.errorById, ...errors } }));
-  },
-
   startDocumentTransfer: (fromAgentId, toAgentId, toolName) => {
     const startedAt = performance.now();
     const id = `${Date.now()}-${documentTransferSeq++}`;
@@ -288,8 +178,9 @@ export const useAgentStore = create<AgentState>((set) => ({
     set({ documentTransfers: [] });
   },
 
-  setLastActiveAgent: (id) => {
-    set({ lastActiveAgentId: id });
+  restoreAgents: (states) => {
+    // Agents the backend has seen stay in the office even when idle.
+    set((state) => mergeAgentStates(state, states, true));
   },
 
   resetAllToIdle: () => {
@@ -326,18 +217,6 @@ export const useAgentStore = create<AgentState>((set) => ({
     });
   },
 
-  recordToolCall: (id) => {
-    set((state) => ({
-      lastToolCallAtById: { ...state.lastToolCallAtById, [id]: Date.now() },
-    }));
-  },
-
-  recordError: (id) => {
-    set((state) => ({
-      lastErrorAtById: { ...state.lastErrorAtById, [id]: Date.now() },
-    }));
-  },
-
   enqueueEffect: (agentId, kind, color, durationMs = 800) => {
     const now = performance.now();
     const key = `${agentId}-${kind}`;
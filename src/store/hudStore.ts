@@ -1,5 +1,5 @@
 import { create } from "zustand";
-import type { LogEntry } from "../types";
+import type { HudCounters, LogEntry } from "../types";
 
 // Event tracking with timestamp for 60-second window
 interface RecentEvent {
@@ -35,6 +35,7 @@ interface HudState {
   recordAgentSwitch: () => void;
   recordEventsBatch: (entries: LogEntry[], agentSwitchCount: number) => void;
   setRateLimitActive: (active: boolean) => void;
+  restoreCounters: (hud: HudCounters) => void;
   pruneOldData: () => void;
 
   // Computed metrics
@@ -176,6 +177,19 @@ export const useHudStore = create<HudState>((set, get) => ({
     set({ rateLimitActive: active });
   },
 
+  restoreCounters: (hud) => {
+    // The backend only keeps counts, so restored events are stamped "now".
+    const now = Date.now();
+    set({
+      recentEvents: [
+        ...Array(hud.tool_calls).fill({ type: "tool_call" as const, timestamp: now }),
+        ...Array(hud.errors).fill({ type: "error" as const, timestamp: now }),
+        ...Array(hud.handoffs).fill({ type: "agent_switch" as const, timestamp: now }),
+      ].slice(-MAX_RECENT_EVENTS),
+      rateLimitActive: hud.rate_limited,
+    });
+  },
+
   pruneOldData: () => {
     const cutoff = Date.now() - WINDOW_MS;
     set((state) => {
@@ -1,4 +1,5 @@
-export { useAgentStore, type DocumentTransfer, type BatchUpdateData, type EffectKind, type VisualEffect } from "./agentStore";
+export { useAgentStore, type DocumentTransfer, type EffectKind, type VisualEffect } from "./agentStore";
 export { useLogStore } from "./logStore";
 export { useSettingsStore } from "./settingsStore";
 export { useHudStore, startHudPruning, stopHudPruning, type HudMetrics } from "./hudStore";
+export { selectActiveTodos, useTodoStore } from "./todoStore";
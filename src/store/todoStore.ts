@@ -0,0 +1,44 @@
+import { create } from "zustand";
+import type { SessionTodos, TodoItem } from "../types";
+
+interface TodoState {
+  /** Latest TodoWrite plan per session */
+  todosBySession: Record<string, TodoItem[]>;
+  /** Session whose plan changed last */
+  latestSessionId: string | null;
+  setSessionTodos: (sessionId: string, todos: TodoItem[]) => void;
+  loadTodos: (sessions: SessionTodos[]) => void;
+}
+
+export const useTodoStore = create<TodoState>((set) => ({
+  todosBySession: {},
+  latestSessionId: null,
+
+  setSessionTodos: (sessionId, todos) => {
+    set((state) => ({
+      todosBySession: { ...state.todosBySession, [sessionId]: todos },
+      latestSessionId: sessionId,
+    }));
+
+/** Plan for the watched session, else the most recently updated one */
+export function selectActiveTodos(state: TodoState, sessionId: string | null): TodoItem[] | null {
+  return (sessionId ? state.todosBySession[sessionId] : undefined)
+    ?? (state.latestSessionId ? state.todosBySession[state.latestSessionId] : undefined)
+    ?? null;
+}
+  },
+
+  loadTodos: (sessions) => {
+    set({
+      todosBySession: Object.fromEntries(sessions.map((s) => [s.session_id, s.todos])),
+      latestSessionId: sessions.length > 0 ? sessions[sessions.length - 1].session_id : null,
+    });
+  },
+}));
+
+/** Plan for the watched session, else the most recently updated one */
+export function selectActiveTodos(state: TodoState, sessionId: string | null): TodoItem[] | null {
+  return (sessionId ? state.todosBySession[sessionId] : undefined)
+    ?? (state.latestSessionId ? state.todosBySession[state.latestSessionId] : undefined)
+    ?? null;
+}
@@ -0,0 +1,121 @@
+// Generated from src-tauri/src/models by `UPDATE_BINDINGS=1 cargo test bindings`. Do not edit.
+
+export type AgentType = "explorer" | "analyzer" | "architect" | "developer" | "operator" | "validator" | "connector" | "liaison";
+
+export type AgentStatus = "idle" | "working" | "thinking" | "passing" | "error";
+
+export type Agent = { id: string, agent_type: AgentType, status: AgentStatus, current_task: string | null, desk_position: [number, number], };
+
+export type AgentState = { agent: Agent, 
+/**
+ * Timestamp of the latest entry routed to this agent
+ */
+last_activity: string | null, last_tool_call: string | null, last_error: string | null, 
+/**
+ * Errors since the last successful tool call or result
+ */
+error_count: number, 
+/**
+ * The usage limit was reached and no activity has resumed yet
+ */
+rate_limited: boolean, };
+
+export type LogEntryType = "tool_call" | "tool_result" | "message" | "error" | "todo_update" | "session_start" | "session_end";
+
+export type TimestampSource = "logged" | "local_time" | "file_modified" | "ingested";
+
+export type ToolDetail = { file_path: string, 
+/**
+ * Unified diff hunks (`@@ ... @@`, ` `/`-`/`+` lines), capped in size
+ */
+diff: string, lines_added: number, lines_removed: number, 
+/**
+ * The diff text was cut to fit the size cap (line counts are still exact)
+ */
+truncated: boolean, };
+
+export type LogEntry = { timestamp: string, 
+/**
+ * How `timestamp` was derived
+ */
+timestamp_source: TimestampSource, entry_type: LogEntryType, content: string, agent_id: string | null, tool_name: string | null, 
+/**
+ * Claude's `toolu_...` id linking a tool call to its result (transcripts and hooks only)
+ */
+tool_use_id: string | null, 
+/**
+ * Claude session the entry belongs to (transcript `sessionId`, hook payload, or log file stem)
+ */
+session_id: string | null, 
+/**
+ * Repository path decoded from the `~/.claude/projects/<project>` folder
+ */
+project: string | null, 
+/**
+ * Working directory recorded with the entry (transcript `cwd` or hook payload)
+ */
+cwd: string | null, 
+/**
+ * Transcript `gitBranch`
+ */
+git_branch: string | null, 
+/**
+ * Size-capped preview of what an Edit/MultiEdit/Write call changes
+ */
+tool_detail?: ToolDetail | null, };
+
+export type TodoStatus = "pending" | "in_progress" | "completed";
+
+export type TodoItem = { content: string, status: TodoStatus, 
+/**
+ * Present-tense label shown while the item is in progress
+ */
+activeForm: string, };
+
+export type TodoStatusChange = { content: string, from: TodoStatus, to: TodoStatus, };
+
+export type TodoDiff = { added: Array<TodoItem>, removed: Array<TodoItem>, status_changed: Array<TodoStatusChange>, };
+
+export type HandoffReason = "shared_file" | "delegation";
+
+export type Handoff = { from: string, to: string, reason: HandoffReason, 
+/**
+ * The shared file path, or the delegated task description
+ */
+artifact: string | null, 
+/**
+ * Tool the receiving agent called
+ */
+tool_name: string | null, };
+
+export type AppEvent = { "type": "LogEntry", "payload": LogEntry } | { "type": "AgentUpdate", "payload": AgentState } | { "type": "SessionStart", "payload": { session_id: string, } } | { "type": "SessionEnd", "payload": { session_id: string, } } | { "type": "WatcherStatus", "payload": { active: boolean, path: string, } } | { "type": "BatchUpdate", "payload": { logs: Array<LogEntry>, agents: Array<AgentState>, } } | { "type": "Handoff", "payload": Handoff } | { "type": "TodoListUpdate", "payload": { session_id: string, todos: Array<TodoItem>, diff: TodoDiff, } };
+
+export type SequencedEvent = { seq: number, } & ({ "type": "LogEntry", "payload": LogEntry } | { "type": "AgentUpdate", "payload": AgentState } | { "type": "SessionStart", "payload": { session_id: string, } } | { "type": "SessionEnd", "payload": { session_id: string, } } | { "type": "WatcherStatus", "payload": { active: boolean, path: string, } } | { "type": "BatchUpdate", "payload": { logs: Array<LogEntry>, agents: Array<AgentState>, } } | { "type": "Handoff", "payload": Handoff } | { "type": "TodoListUpdate", "payload": { session_id: string, todos: Array<TodoItem>, diff: TodoDiff, } });
+
+export type SubscriptionFilter = { sessions: Array<string>, entry_types: Array<LogEntryType>, 
+/**
+ * Office agent ids, e.g. `developer`
+ */
+agent_ids: Array<string>, };
+
+export type WatcherInfo = { active: boolean, path: string, };
+
+export type HudCounters = { tool_calls: number, tool_results: number, errors: number, handoffs: number, rate_limited: boolean, };
+
+export type OfficeSnapshot = { 
+/**
+ * Sequence number of the last event reflected here
+ */
+seq: number, 
+/**
+ * Agents seen so far, including idle ones
+ */
+agents: Array<AgentState>, 
+/**
+ * Recent log entries, oldest first
+ */
+logs: Array<LogEntry>, 
+/**
+ * Active session ids, most recent first
+ */
+sessions: Array<string>, watcher: WatcherInfo | null, hud: HudCounters, };
@@ -1,39 +1,194 @@
-export type AgentType = "explorer" | "analyzer" | "architect" | "developer" | "operator" | "validator" | "connector" | "liaison";
+// Event payload types are generated from the Rust models (src-tauri/src/bindings.rs).
+import type { AgentType, LogEntry, LogEntryType, TodoItem } from "./generated";
 
-export type AgentStatus = "idle" | "working" | "thinking" | "passing" | "error";
+export type {
+  Agent,
+  AgentState,
+  AgentStatus,
+  AgentType,
+  AppEvent,
+  Handoff,
+  HandoffReason,
+  HudCounters,
+  LogEntry,
+  LogEntryType,
+  OfficeSnapshot,
+  SequencedEvent,
+  SubscriptionFilter,
+  TimestampSource,
+  TodoDiff,
+  TodoItem,
+  TodoStatus,
+  TodoStatusChange,
+  ToolDetail,
+  WatcherInfo,
+} from "./generated";
 
-export interface Agent {
+export interface SessionTodos {
+  session_id: string;
+  todos: TodoItem[];
+}
+
+export interface NotificationSettings {
+  enabled: boolean;
+  session_finished: boolean;
+  session_idle_secs: number;
+  error_burst: boolean;
+  error_burst_count: number;
+  error_burst_window_secs: number;
+  rate_limit: boolean;
+  permission_needed: boolean;
+  long_running_tool: boolean;
+  long_running_tool_secs: number;
+}
+
+export interface ServerSettings {
+  enabled: boolean;
+  host: string;
+  port: number;
+  allowed_origins: string[];
+}
+
+export interface IngestSettings {
+  enabled: boolean;
+  port: number;
+}
+
+export interface MetricsSettings {
+  enabled: boolean;
+  port: number;
+}
+
+export interface TelemetrySettings {
+  enabled: boolean;
+  endpoint: string;
+  service_name: string;
+  session_idle_secs: number;
+}
+
+export interface RedactionSettings {
+  enabled: boolean;
+  /** Extra regexes masked as `[REDACTED]` */
+  patterns: string[];
+}
+
+export interface ProjectSettings {
+  /** Encoded project folder names to watch; empty watches every project */
+  watched: string[];
+}
+
+/** Every condition that is set must match; globs use `*`, `?`, `**` and `[...]` */
+export interface FilterRule {
+  path?: string | null;
+  project?: string | null;
+  tool?: string | null;
+  entry_types?: LogEntryType[];
+}
+
+/** Excludes win; when includes are set, an entry must match one of them */
+export interface FilterSettings {
+  include: FilterRule[];
+  exclude: FilterRule[];
+}
+
+/** Timed agent transitions applied by the backend office state */
+export interface OfficeSettings {
+  working_timeout_secs: number;
+  thinking_timeout_secs: number;
+  error_decay_secs: number;
+  passing_secs: number;
+}
+
+/** Backend settings persisted by the Rust side (see `settings::AppSettings`) */
+export interface AppSettings {
+  notifications: NotificationSettings;
+  server: ServerSettings;
+  ingest: IngestSettings;
+  metrics: MetricsSettings;
+  telemetry: TelemetrySettings;
+  redaction: RedactionSettings;
+  projects: ProjectSettings;
+  filters: FilterSettings;
+  office: OfficeSettings;
+}
+
+export interface SessionInfo {
+  session_id: string;
+  cwd: string | null;
+  git_branch: string | null;
+  last_active: string | null;
+}
+
+/** A repository with Claude Code sessions (see `list_projects`) */
+export interface ProjectInfo {
+  /** Folder name under `~/.claude/projects` */
   id: string;
+  path: string;
+  name: string;
+  sessions: SessionInfo[];
+  watched: boolean;
+}
+
+export type ExportFormat = "ndjson" | "csv" | "json";
+
+export type ExportColumn =
+  | "timestamp"
+  | "session_id"
+  | "entry_type"
+  | "agent_type"
+  | "agent_status"
+  | "tool_name"
+  | "tool_use_id"
+  | "agent_id"
+  | "project"
+  | "git_branch"
+  | "content";
+
+export interface ExportQuery {
+  session_id?: string | null;
+  from?: string | null;
+  to?: string | null;
+  format?: ExportFormat;
+  /** Output order; all columns when empty */
+  columns?: ExportColumn[];
+}
+
+export interface SearchQuery {
+  /** Case-insensitive; empty matches everything */
+  text?: string;
+  session_id?: string | null;
+  /** Substring of the ~/.claude/projects folder name */
+  project?: string | null;
+  agent_types?: AgentType[];
+  entry_types?: LogEntryType[];
+  from?: string | null;
+  to?: string | null;
+  offset?: number;
+  limit?: number | null;
+}
+
+export interface SearchHit {
+  entry: LogEntry;
+  project: string;
   agent_type: AgentType;
-  status: AgentStatus;
-  current_task: string | null;
-  desk_position: [number, number];
-}
-
-export type LogEntryType =
-  | "tool_call"
-  | "tool_result"
-  | "message"
-  | "error"
-  | "todo_update"
-  | "session_start"
-  | "session_end";
-
-export interface LogEntry {
-  timestamp: string;
-  entry_type: LogEntryType;
-  content: string;
-  agent_id: string | null;
-  tool_name: string | null;
+  snippet: string;
 }
 
-export type AppEvent =
-  | { type: "LogEntry"; payload: LogEntry }
-  | { type: "AgentUpdate"; payload: Agent }
-  | { type: "SessionStart"; payload: { session_id: string } }
-  | { type: "SessionEnd"; payload: { session_id: string } }
-  | { type: "WatcherStatus"; payload: { active: boolean; path: string } }
-  | { type: "BatchUpdate"; payload: { logs: LogEntry[]; agents: Agent[] } };
+export interface SearchPage {
+  hits: SearchHit[];
+  total: number;
+  offset: number;
+  limit: number;
+}
+
+export interface FileActivity {
+  session_id: string;
+  path: string;
+  reads: number;
+  edits: number;
+  writes: number;
+  last_touched: string;
+}
 
 export interface DeskConfig {
   id: string;